@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use core_sol_bond_stake_client::{accounts, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+fn fetch(client: &RpcClient, address: &Pubkey) -> Result<Vec<u8>> {
+    let account = client
+        .get_account(address)
+        .with_context(|| format!("failed to fetch account {}", address))?;
+
+    Ok(account.data)
+}
+
+fn print_field(name: &str, value: impl std::fmt::Display) {
+    println!("{:<32}{}", name, value);
+}
+
+pub fn bond_config(client: &RpcClient, index: u8) -> Result<()> {
+    let address = pda::find_bond_config(index).0;
+    let bond_config = accounts::deserialize_bond_config(&fetch(client, &address)?)?;
+
+    print_field("address", address);
+    print_field("index", bond_config.index);
+    print_field("bond_state", bond_config.bond_state);
+    print_field("merkle_tree", bond_config.merkle_tree);
+    print_field("lock_period", bond_config.lock_period);
+    print_field("bond_amount", bond_config.bond_amount);
+    print_field("withdraw_penalty", bond_config.withdraw_penalty);
+    print_field("asset_mode", bond_config.asset_mode);
+    print_field("cooldown_period", bond_config.cooldown_period);
+    print_field("pause_flags", bond_config.pause_flags);
+
+    Ok(())
+}
+
+pub fn rewards_config(client: &RpcClient) -> Result<()> {
+    let address = pda::find_rewards_config().0;
+    let rewards_config = accounts::deserialize_rewards_config(&fetch(client, &address)?)?;
+
+    print_field("address", address);
+    print_field("rewards_state", rewards_config.rewards_state);
+    print_field("rewards_reserve", rewards_config.rewards_reserve);
+    print_field("accumulated_rewards", rewards_config.accumulated_rewards);
+    print_field("rewards_per_slot", rewards_config.rewards_per_slot);
+    print_field("rewards_per_share", rewards_config.rewards_per_share);
+    print_field("last_reward_slot", rewards_config.last_reward_slot);
+    print_field("max_apr", rewards_config.max_apr);
+    print_field("liveliness_curve", rewards_config.liveliness_curve);
+    print_field("liveliness_threshold", rewards_config.liveliness_threshold);
+    print_field(
+        "liveliness_curve_param",
+        rewards_config.liveliness_curve_param,
+    );
+
+    Ok(())
+}
+
+pub fn vault_config(client: &RpcClient) -> Result<()> {
+    let address = pda::find_vault_config().0;
+    let vault_config = accounts::deserialize_vault_config(&fetch(client, &address)?)?;
+
+    print_field("address", address);
+    print_field("vault", vault_config.vault);
+    print_field("mint_of_token", vault_config.mint_of_token);
+    print_field("total_bond_amount", vault_config.total_bond_amount);
+    print_field(
+        "total_penalized_amount",
+        vault_config.total_penalized_amount,
+    );
+    print_field(
+        "total_challenge_amount",
+        vault_config.total_challenge_amount,
+    );
+
+    Ok(())
+}
+
+pub fn program_config(client: &RpcClient) -> Result<()> {
+    let address = pda::find_program_config().0;
+    let program_config = accounts::deserialize_program_config(&fetch(client, &address)?)?;
+
+    print_field("address", address);
+    print_field("slashing_authority", program_config.slashing_authority);
+    print_field("slash_share", program_config.slash_share);
+    print_field("slash_destination", program_config.slash_destination);
+    print_field("arbiter", program_config.arbiter);
+    print_field("challenge_amount", program_config.challenge_amount);
+    print_field("emergency_state", program_config.emergency_state);
+    print_field("pause_flags", program_config.pause_flags);
+    print_field("challenge_period", program_config.challenge_period);
+
+    Ok(())
+}
+
+pub fn address_bonds_rewards(client: &RpcClient, address: &Pubkey) -> Result<()> {
+    let account = pda::find_address_bonds_rewards(address).0;
+    let address_bonds_rewards =
+        accounts::deserialize_address_bonds_rewards(&fetch(client, &account)?)?;
+
+    print_field("account", account);
+    print_field("address", address_bonds_rewards.address);
+    print_field(
+        "address_total_bond_amount",
+        address_bonds_rewards.address_total_bond_amount,
+    );
+    print_field("current_index", address_bonds_rewards.current_index);
+    print_field(
+        "last_update_timestamp",
+        address_bonds_rewards.last_update_timestamp,
+    );
+    print_field(
+        "address_rewards_per_share",
+        address_bonds_rewards.address_rewards_per_share,
+    );
+    print_field("claimable_amount", address_bonds_rewards.claimable_amount);
+    print_field("vault_bond_id", address_bonds_rewards.vault_bond_id);
+    print_field("open_bonds", address_bonds_rewards.open_bonds);
+    print_field("keeper", address_bonds_rewards.keeper);
+    print_field(
+        "liveliness_unbond_sum",
+        address_bonds_rewards.liveliness_unbond_sum,
+    );
+    print_field("liveliness_amount", address_bonds_rewards.liveliness_amount);
+    print_field(
+        "liveliness_auto_renew_lock_sum",
+        address_bonds_rewards.liveliness_auto_renew_lock_sum,
+    );
+    print_field(
+        "liveliness_lock_sum",
+        address_bonds_rewards.liveliness_lock_sum,
+    );
+    print_field("synced_index", address_bonds_rewards.synced_index);
+
+    Ok(())
+}
+
+pub fn bond(client: &RpcClient, owner: &Pubkey, bond_id: u16) -> Result<()> {
+    let address = pda::find_bond(owner, bond_id).0;
+    let bond = accounts::deserialize_bond(&fetch(client, &address)?)?;
+
+    print_field("address", address);
+    print_field("state", bond.state);
+    print_field("bond_timestamp", bond.bond_timestamp);
+    print_field("unbond_timestamp", bond.unbond_timestamp);
+    print_field("bond_amount", bond.bond_amount);
+    print_field("asset_id", bond.asset_id);
+    print_field("owner", bond.owner);
+    print_field("asset_mode", bond.asset_mode);
+    print_field("slash_reason", bond.slash_reason);
+    print_field("challenged", bond.challenged);
+    print_field("cooldown_end_timestamp", bond.cooldown_end_timestamp);
+    print_field("unbond_penalty", bond.unbond_penalty);
+    print_field("auto_renew", bond.auto_renew);
+    print_field("counted", bond.counted);
+    print_field("lock_expired", bond.lock_expired);
+
+    Ok(())
+}
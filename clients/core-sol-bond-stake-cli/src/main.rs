@@ -0,0 +1,349 @@
+mod inspect;
+mod transaction;
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use core_sol_bond_stake_client::{accounts, instructions, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use transaction::Submitter;
+
+#[derive(Parser)]
+#[command(about = "Operate the Itheum Bonding and Staking Program")]
+struct Cli {
+    #[arg(long, default_value = "https://api.devnet.solana.com")]
+    url: String,
+
+    /// Keypair signing and paying for the transaction
+    #[arg(long, default_value_t = default_keypair())]
+    keypair: String,
+
+    /// Print the transaction base64 encoded and unsigned instead of sending it
+    #[arg(long, requires = "authority")]
+    unsigned: bool,
+
+    /// Authority and fee payer of unsigned transactions, e.g. a multisig vault
+    #[arg(long)]
+    authority: Option<Pubkey>,
+
+    /// Durable nonce account used instead of a recent blockhash for unsigned transactions
+    #[arg(long, requires = "unsigned")]
+    nonce: Option<Pubkey>,
+
+    /// Authority of the nonce account, defaults to --authority
+    #[arg(long, requires = "nonce")]
+    nonce_authority: Option<Pubkey>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StateArg {
+    Active,
+    Inactive,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    InitializeContract {
+        #[arg(long)]
+        merkle_tree: Pubkey,
+        #[arg(long)]
+        index: u8,
+        #[arg(long)]
+        lock_period: u64,
+        #[arg(long)]
+        bond_amount: u64,
+        #[arg(long)]
+        rewards_per_slot: u64,
+        #[arg(long)]
+        max_apr: u64,
+        #[arg(long)]
+        withdraw_penalty: u64,
+    },
+    InitializeVault {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    InitializeProgramConfig {
+        #[arg(long)]
+        slashing_authority: Pubkey,
+        #[arg(long)]
+        slash_share: u64,
+    },
+    CreateBondConfig {
+        #[arg(long)]
+        index: u8,
+        #[arg(long)]
+        merkle_tree: Pubkey,
+        #[arg(long)]
+        lock_period: u64,
+        #[arg(long)]
+        bond_amount: u64,
+        #[arg(long)]
+        withdraw_penalty: u64,
+    },
+    SetBondState {
+        #[arg(long)]
+        index: u8,
+        #[arg(value_enum)]
+        state: StateArg,
+    },
+    /// Every given value is updated in a single transaction
+    UpdateBondConfig {
+        #[arg(long)]
+        index: u8,
+        #[arg(long)]
+        merkle_tree: Option<Pubkey>,
+        #[arg(long)]
+        lock_period: Option<u64>,
+        #[arg(long)]
+        bond_amount: Option<u64>,
+        #[arg(long)]
+        withdraw_penalty: Option<u64>,
+        #[arg(long)]
+        asset_mode: Option<u8>,
+        #[arg(long)]
+        cooldown_period: Option<u64>,
+        #[arg(long)]
+        pause_flags: Option<u8>,
+    },
+    AddRewards {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    RemoveRewards {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    SetRewardsState {
+        #[arg(value_enum)]
+        state: StateArg,
+    },
+    UpdateRewardsPerSlot {
+        #[arg(long)]
+        rewards_per_slot: u64,
+    },
+    UpdateMaxApr {
+        #[arg(long)]
+        max_apr: u64,
+    },
+    UpdateLivelinessCurve {
+        #[arg(long)]
+        curve: u8,
+        #[arg(long)]
+        threshold: u64,
+        #[arg(long, default_value_t = 0)]
+        param: u64,
+    },
+    /// Moves the rewards config to the u128 rewards per share layout
+    MigrateRewardsConfig,
+    /// Counts the address' bonds created before open bond tracking, in order
+    SyncAddress {
+        #[arg(long)]
+        address: Pubkey,
+        /// Bonds synced per transaction
+        #[arg(long, default_value_t = 10)]
+        count: u16,
+    },
+    /// Fetch and print a program account
+    Show {
+        #[command(subcommand)]
+        account: ShowCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ShowCommand {
+    BondConfig {
+        #[arg(long)]
+        index: u8,
+    },
+    RewardsConfig,
+    VaultConfig,
+    ProgramConfig,
+    Address {
+        #[arg(long)]
+        address: Pubkey,
+    },
+    Bond {
+        #[arg(long)]
+        owner: Pubkey,
+        #[arg(long)]
+        bond_id: u16,
+    },
+}
+
+fn default_keypair() -> String {
+    format!(
+        "{}/.config/solana/id.json",
+        std::env::var("HOME").unwrap_or_default()
+    )
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    let command = match cli.command {
+        Command::Show { account } => return show(&client, account),
+        command => command,
+    };
+
+    let submitter = Submitter::from_args(
+        &cli.keypair,
+        cli.unsigned,
+        cli.authority,
+        cli.nonce,
+        cli.nonce_authority,
+    )?;
+    let authority = submitter.authority();
+
+    let instructions = match command {
+        Command::InitializeContract {
+            merkle_tree,
+            index,
+            lock_period,
+            bond_amount,
+            rewards_per_slot,
+            max_apr,
+            withdraw_penalty,
+        } => vec![instructions::initialize_contract(
+            &authority,
+            &merkle_tree,
+            index,
+            lock_period,
+            bond_amount,
+            rewards_per_slot,
+            max_apr,
+            withdraw_penalty,
+        )],
+        Command::InitializeVault { mint } => {
+            vec![instructions::initialize_vault(&authority, &mint)]
+        }
+        Command::InitializeProgramConfig {
+            slashing_authority,
+            slash_share,
+        } => vec![instructions::initialize_program_config(
+            &authority,
+            &slashing_authority,
+            slash_share,
+        )],
+        Command::CreateBondConfig {
+            index,
+            merkle_tree,
+            lock_period,
+            bond_amount,
+            withdraw_penalty,
+        } => vec![instructions::create_bond_config(
+            &authority,
+            &merkle_tree,
+            index,
+            lock_period,
+            bond_amount,
+            withdraw_penalty,
+        )],
+        Command::SetBondState { index, state } => vec![match state {
+            StateArg::Active => instructions::set_bond_state_active(&authority, index),
+            StateArg::Inactive => instructions::set_bond_state_inactive(&authority, index),
+        }],
+        Command::UpdateBondConfig {
+            index,
+            merkle_tree,
+            lock_period,
+            bond_amount,
+            withdraw_penalty,
+            asset_mode,
+            cooldown_period,
+            pause_flags,
+        } => {
+            let instructions = [
+                merkle_tree
+                    .map(|value| instructions::update_merkle_tree(&authority, index, &value)),
+                lock_period.map(|value| instructions::update_lock_period(&authority, index, value)),
+                bond_amount.map(|value| instructions::update_bond_amount(&authority, index, value)),
+                withdraw_penalty
+                    .map(|value| instructions::update_withdraw_penalty(&authority, index, value)),
+                asset_mode.map(|value| instructions::update_asset_mode(&authority, index, value)),
+                cooldown_period
+                    .map(|value| instructions::update_cooldown_period(&authority, index, value)),
+                pause_flags.map(|value| instructions::update_pause_flags(&authority, index, value)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+            if instructions.is_empty() {
+                bail!("nothing to update");
+            }
+
+            instructions
+        }
+        Command::AddRewards { mint, amount } => {
+            vec![instructions::add_rewards(&authority, &mint, amount)]
+        }
+        Command::RemoveRewards { mint, amount } => {
+            vec![instructions::remove_rewards(&authority, &mint, amount)]
+        }
+        Command::SetRewardsState { state } => vec![match state {
+            StateArg::Active => instructions::set_rewards_state_active(&authority),
+            StateArg::Inactive => instructions::set_rewards_state_inactive(&authority),
+        }],
+        Command::UpdateRewardsPerSlot { rewards_per_slot } => {
+            vec![instructions::update_rewards_per_slot(
+                &authority,
+                rewards_per_slot,
+            )]
+        }
+        Command::UpdateMaxApr { max_apr } => {
+            vec![instructions::update_max_apr(&authority, max_apr)]
+        }
+        Command::UpdateLivelinessCurve {
+            curve,
+            threshold,
+            param,
+        } => vec![instructions::update_liveliness_curve(
+            &authority, curve, threshold, param,
+        )],
+        Command::MigrateRewardsConfig => {
+            vec![instructions::migrate_rewards_config(&authority)]
+        }
+        Command::SyncAddress { address, count } => {
+            let account = pda::find_address_bonds_rewards(&address).0;
+            let address_bonds_rewards =
+                accounts::deserialize_address_bonds_rewards(&client.get_account_data(&account)?)?;
+            let first_bond_id = address_bonds_rewards.synced_index + 1;
+            let last_bond_id = address_bonds_rewards
+                .current_index
+                .min(address_bonds_rewards.synced_index.saturating_add(count));
+
+            if first_bond_id > last_bond_id {
+                bail!("address already synced");
+            }
+
+            (first_bond_id..=last_bond_id)
+                .map(|bond_id| instructions::sync_bond(&address, bond_id))
+                .collect()
+        }
+        Command::Show { .. } => unreachable!(),
+    };
+
+    submitter.submit(&client, &instructions)
+}
+
+fn show(client: &RpcClient, account: ShowCommand) -> Result<()> {
+    match account {
+        ShowCommand::BondConfig { index } => inspect::bond_config(client, index),
+        ShowCommand::RewardsConfig => inspect::rewards_config(client),
+        ShowCommand::VaultConfig => inspect::vault_config(client),
+        ShowCommand::ProgramConfig => inspect::program_config(client),
+        ShowCommand::Address { address } => inspect::address_bonds_rewards(client, &address),
+        ShowCommand::Bond { owner, bond_id } => inspect::bond(client, &owner, bond_id),
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::{nonce_utils, rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+// durable nonce account and its authority, unsigned transactions outlive a recent blockhash
+pub struct Nonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+// signs with the local keypair, or prints the transaction for multisig submission
+pub enum Submitter {
+    Keypair(Keypair),
+    Unsigned {
+        fee_payer: Pubkey,
+        nonce: Option<Nonce>,
+    },
+}
+
+impl Submitter {
+    pub fn from_args(
+        keypair: &str,
+        unsigned: bool,
+        authority: Option<Pubkey>,
+        nonce: Option<Pubkey>,
+        nonce_authority: Option<Pubkey>,
+    ) -> Result<Self> {
+        if unsigned {
+            let fee_payer = authority.context("--authority is required with --unsigned")?;
+            let nonce = nonce.map(|account| Nonce {
+                account,
+                authority: nonce_authority.unwrap_or(fee_payer),
+            });
+
+            return Ok(Submitter::Unsigned { fee_payer, nonce });
+        }
+
+        let keypair = read_keypair_file(keypair)
+            .map_err(|err| anyhow::anyhow!("failed to read keypair {}: {}", keypair, err))?;
+
+        Ok(Submitter::Keypair(keypair))
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        match self {
+            Submitter::Keypair(keypair) => keypair.pubkey(),
+            Submitter::Unsigned { fee_payer, .. } => *fee_payer,
+        }
+    }
+
+    pub fn submit(&self, client: &RpcClient, instructions: &[Instruction]) -> Result<()> {
+        match self {
+            Submitter::Keypair(keypair) => {
+                let blockhash = client
+                    .get_latest_blockhash()
+                    .context("failed to fetch latest blockhash")?;
+                let transaction = Transaction::new_signed_with_payer(
+                    instructions,
+                    Some(&keypair.pubkey()),
+                    &[keypair],
+                    blockhash,
+                );
+                let signature = client
+                    .send_and_confirm_transaction(&transaction)
+                    .context("transaction failed")?;
+
+                println!("{}", signature);
+            }
+            Submitter::Unsigned {
+                fee_payer,
+                nonce: None,
+            } => {
+                let blockhash = client
+                    .get_latest_blockhash()
+                    .context("failed to fetch latest blockhash")?;
+                let message =
+                    Message::new_with_blockhash(instructions, Some(fee_payer), &blockhash);
+
+                print_unsigned(message)?;
+            }
+            Submitter::Unsigned {
+                fee_payer,
+                nonce: Some(nonce),
+            } => {
+                let account = nonce_utils::get_account(client, &nonce.account)
+                    .context("failed to fetch nonce account")?;
+                let data = nonce_utils::data_from_account(&account)
+                    .context("failed to read nonce account")?;
+
+                if data.authority != nonce.authority {
+                    bail!(
+                        "nonce authority is {}, not {}",
+                        data.authority,
+                        nonce.authority
+                    );
+                }
+
+                // advances the nonce first, the stored nonce stands in for the blockhash
+                let mut message = Message::new_with_nonce(
+                    instructions.to_vec(),
+                    Some(fee_payer),
+                    &nonce.account,
+                    &nonce.authority,
+                );
+                message.recent_blockhash = data.blockhash();
+
+                print_unsigned(message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_unsigned(message: Message) -> Result<()> {
+    let transaction = Transaction::new_unsigned(message);
+
+    println!("{}", STANDARD.encode(bincode::serialize(&transaction)?));
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anchor_lang::{AccountDeserialize, Result, Space};
+use core_sol_bond_stake_sc::states::{
+    AddressBondsRewards, AssetUsage, Bond, BondConfig, Challenge, ProgramConfig, RewardsConfig,
+    VaultConfig,
+};
+use core_sol_bond_stake_sc::utils::widen_rewards_per_share;
+
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+// accounts created with an older layout read their new trailing fields as zero
+fn deserialize_padded<T: AccountDeserialize + Space>(data: &[u8]) -> Result<T> {
+    if data.len() >= T::INIT_SPACE {
+        return deserialize(data);
+    }
+
+    let mut padded = data.to_vec();
+    padded.resize(T::INIT_SPACE, 0);
+
+    deserialize(&padded)
+}
+
+pub fn deserialize_bond_config(data: &[u8]) -> Result<BondConfig> {
+    deserialize(data)
+}
+
+pub fn deserialize_rewards_config(data: &[u8]) -> Result<RewardsConfig> {
+    deserialize(data)
+}
+
+pub fn deserialize_vault_config(data: &[u8]) -> Result<VaultConfig> {
+    deserialize(data)
+}
+
+pub fn deserialize_program_config(data: &[u8]) -> Result<ProgramConfig> {
+    deserialize(data)
+}
+
+// unmigrated accounts still hold a u64 rewards per share, read them as migrate_address
+// would leave them
+pub fn deserialize_address_bonds_rewards(data: &[u8]) -> Result<AddressBondsRewards> {
+    if data.len() >= AddressBondsRewards::INIT_SPACE {
+        return deserialize(data);
+    }
+
+    let mut migrated = data.to_vec();
+    migrated.resize(AddressBondsRewards::INIT_SPACE, 0);
+    widen_rewards_per_share(&mut migrated, AddressBondsRewards::REWARDS_PER_SHARE_OFFSET);
+
+    deserialize(&migrated)
+}
+
+pub fn deserialize_bond(data: &[u8]) -> Result<Bond> {
+    deserialize(data)
+}
+
+pub fn deserialize_asset_usage(data: &[u8]) -> Result<AssetUsage> {
+    deserialize_padded(data)
+}
+
+pub fn deserialize_challenge(data: &[u8]) -> Result<Challenge> {
+    deserialize(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use core_sol_bond_stake_sc::{
+        constants::{DIVISION_SAFETY_CONST, REWARDS_PER_SHARE_PRECISION},
+        states::fixtures::filled_address_bonds_rewards,
+    };
+
+    #[test]
+    fn legacy_address_bonds_rewards_is_widened() {
+        let scale = REWARDS_PER_SHARE_PRECISION / DIVISION_SAFETY_CONST as u128;
+        let account = filled_address_bonds_rewards(7 * scale);
+
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+
+        // the unmigrated layout stores a u64 at the legacy scale and is 8 bytes shorter
+        let offset = AddressBondsRewards::REWARDS_PER_SHARE_OFFSET;
+        data.splice(offset..offset + 16, 7u64.to_le_bytes());
+
+        let legacy = deserialize_address_bonds_rewards(&data).unwrap();
+        assert_eq!(legacy.address, account.address);
+        assert_eq!(
+            legacy.address_rewards_per_share,
+            account.address_rewards_per_share
+        );
+        assert_eq!(legacy.claimable_amount, account.claimable_amount);
+        assert_eq!(legacy.keeper, account.keeper);
+        assert_eq!(legacy.liveliness_lock_sum, account.liveliness_lock_sum);
+        assert_eq!(legacy.synced_index, account.synced_index);
+
+        data.clear();
+        account.try_serialize(&mut data).unwrap();
+
+        let current = deserialize_address_bonds_rewards(&data).unwrap();
+        assert_eq!(
+            current.address_rewards_per_share,
+            account.address_rewards_per_share
+        );
+        assert_eq!(current.synced_index, account.synced_index);
+    }
+}
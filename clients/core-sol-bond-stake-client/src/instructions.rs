@@ -0,0 +1,1156 @@
+//! Instruction builders for every program entrypoint, PDAs are derived from the arguments.
+
+use anchor_lang::{system_program, Id, InstructionData, ToAccountMetas};
+use anchor_spl::{associated_token, associated_token::get_associated_token_address, token};
+use core_sol_bond_stake_sc::{accounts, instruction, utils::LeafArgs, BondManyArgs, ID};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_account_compression::{program::SplAccountCompression, Noop};
+
+use crate::pda::{
+    find_address_bonds_rewards, find_asset_authority, find_asset_usage, find_bond,
+    find_bond_config, find_challenge, find_program_config, find_rewards_config, find_tree_config,
+    find_vault_config, get_vault,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn with_remaining_accounts(mut instruction: Instruction, accounts: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(*account, false)),
+    );
+    instruction
+}
+
+// Bond State
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_contract(
+    authority: &Pubkey,
+    merkle_tree: &Pubkey,
+    index: u8,
+    lock_period: u64,
+    bond_amount: u64,
+    rewards_per_slot: u64,
+    max_apr: u64,
+    withdraw_penalty: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeContract {
+            bond_config: find_bond_config(index).0,
+            rewards_config: find_rewards_config().0,
+            merkle_tree: *merkle_tree,
+            authority: *authority,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::InitializeContract {
+            index,
+            lock_period,
+            bond_amount,
+            rewards_per_slot,
+            max_apr,
+            withdraw_penalty,
+        },
+    )
+}
+
+pub fn initialize_vault(authority: &Pubkey, mint_of_token: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeVault {
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token: *mint_of_token,
+            authority: *authority,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::InitializeVault {},
+    )
+}
+
+pub fn create_bond_config(
+    authority: &Pubkey,
+    merkle_tree: &Pubkey,
+    index: u8,
+    lock_period: u64,
+    bond_amount: u64,
+    withdraw_penalty: u64,
+) -> Instruction {
+    build(
+        accounts::CreateBondConfig {
+            bond_config: find_bond_config(index).0,
+            merkle_tree: *merkle_tree,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateBondConfig {
+            index,
+            lock_period,
+            bond_amount,
+            withdraw_penalty,
+        },
+    )
+}
+
+// Update bond config
+
+fn update_bond_config_accounts(authority: &Pubkey, index: u8) -> accounts::UpdateBondConfig {
+    accounts::UpdateBondConfig {
+        bond_config: find_bond_config(index).0,
+        authority: *authority,
+    }
+}
+
+pub fn set_bond_state_active(authority: &Pubkey, index: u8) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::SetBondStateActive { _index: index },
+    )
+}
+
+pub fn set_bond_state_inactive(authority: &Pubkey, index: u8) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::SetBondStateInactive { _index: index },
+    )
+}
+
+pub fn update_merkle_tree(authority: &Pubkey, index: u8, merkle_tree: &Pubkey) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateMerkleTree {
+            _index: index,
+            merkle_tree: *merkle_tree,
+        },
+    )
+}
+
+pub fn update_lock_period(authority: &Pubkey, index: u8, lock_period: u64) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateLockPeriod {
+            _index: index,
+            lock_period,
+        },
+    )
+}
+
+pub fn update_bond_amount(authority: &Pubkey, index: u8, bond_amount: u64) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateBondAmount {
+            _index: index,
+            bond_amount,
+        },
+    )
+}
+
+pub fn update_withdraw_penalty(
+    authority: &Pubkey,
+    index: u8,
+    withdraw_penalty: u64,
+) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateWithdrawPenalty {
+            _index: index,
+            withdraw_penalty,
+        },
+    )
+}
+
+pub fn update_asset_mode(authority: &Pubkey, index: u8, asset_mode: u8) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateAssetMode {
+            _index: index,
+            asset_mode,
+        },
+    )
+}
+
+pub fn update_cooldown_period(authority: &Pubkey, index: u8, cooldown_period: u64) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdateCooldownPeriod {
+            _index: index,
+            cooldown_period,
+        },
+    )
+}
+
+pub fn update_pause_flags(authority: &Pubkey, index: u8, pause_flags: u8) -> Instruction {
+    build(
+        update_bond_config_accounts(authority, index),
+        instruction::UpdatePauseFlags {
+            _index: index,
+            pause_flags,
+        },
+    )
+}
+
+// Program config
+
+pub fn initialize_program_config(
+    authority: &Pubkey,
+    slashing_authority: &Pubkey,
+    slash_share: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeProgramConfig {
+            program_config: find_program_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeProgramConfig {
+            slashing_authority: *slashing_authority,
+            slash_share,
+        },
+    )
+}
+
+fn update_program_config_accounts(authority: &Pubkey) -> accounts::UpdateProgramConfig {
+    accounts::UpdateProgramConfig {
+        program_config: find_program_config().0,
+        authority: *authority,
+    }
+}
+
+pub fn update_slashing_authority(authority: &Pubkey, slashing_authority: &Pubkey) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateSlashingAuthority {
+            slashing_authority: *slashing_authority,
+        },
+    )
+}
+
+pub fn update_slash_share(authority: &Pubkey, slash_share: u64) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateSlashShare { slash_share },
+    )
+}
+
+pub fn update_slash_destination(authority: &Pubkey, slash_destination: u8) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateSlashDestination { slash_destination },
+    )
+}
+
+pub fn update_arbiter(authority: &Pubkey, arbiter: &Pubkey) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateArbiter { arbiter: *arbiter },
+    )
+}
+
+pub fn update_challenge_amount(authority: &Pubkey, challenge_amount: u64) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateChallengeAmount { challenge_amount },
+    )
+}
+
+pub fn update_challenge_period(authority: &Pubkey, challenge_period: u64) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateChallengePeriod { challenge_period },
+    )
+}
+
+pub fn set_emergency_state_active(authority: &Pubkey) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::SetEmergencyStateActive {},
+    )
+}
+
+pub fn set_emergency_state_inactive(authority: &Pubkey) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::SetEmergencyStateInactive {},
+    )
+}
+
+pub fn update_global_pause_flags(authority: &Pubkey, pause_flags: u8) -> Instruction {
+    build(
+        update_program_config_accounts(authority),
+        instruction::UpdateGlobalPauseFlags { pause_flags },
+    )
+}
+
+// Rewards config
+
+fn update_rewards_config_accounts(authority: &Pubkey) -> accounts::UpdateRewardsConfig {
+    accounts::UpdateRewardsConfig {
+        rewards_config: find_rewards_config().0,
+        authority: *authority,
+    }
+}
+
+pub fn set_rewards_state_active(authority: &Pubkey) -> Instruction {
+    build(
+        update_rewards_config_accounts(authority),
+        instruction::SetRewardsStateActive {},
+    )
+}
+
+pub fn set_rewards_state_inactive(authority: &Pubkey) -> Instruction {
+    build(
+        update_rewards_config_accounts(authority),
+        instruction::SetRewardsStateInactive {},
+    )
+}
+
+pub fn update_rewards_per_slot(authority: &Pubkey, rewards_per_slot: u64) -> Instruction {
+    build(
+        update_rewards_config_accounts(authority),
+        instruction::UpdateRewardsPerSlot { rewards_per_slot },
+    )
+}
+
+pub fn update_max_apr(authority: &Pubkey, max_apr: u64) -> Instruction {
+    build(
+        update_rewards_config_accounts(authority),
+        instruction::UpdateMaxApr { max_apr },
+    )
+}
+
+pub fn update_liveliness_curve(
+    authority: &Pubkey,
+    liveliness_curve: u8,
+    liveliness_threshold: u64,
+    liveliness_curve_param: u64,
+) -> Instruction {
+    build(
+        update_rewards_config_accounts(authority),
+        instruction::UpdateLivelinessCurve {
+            liveliness_curve,
+            liveliness_threshold,
+            liveliness_curve_param,
+        },
+    )
+}
+
+fn rewards_accounts(authority: &Pubkey, mint_of_token: &Pubkey) -> accounts::RewardsContext {
+    accounts::RewardsContext {
+        rewards_config: find_rewards_config().0,
+        vault_config: find_vault_config().0,
+        vault: get_vault(mint_of_token),
+        mint_of_token: *mint_of_token,
+        authority: *authority,
+        authority_token_account: get_associated_token_address(authority, mint_of_token),
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+    }
+}
+
+pub fn add_rewards(authority: &Pubkey, mint_of_token: &Pubkey, amount: u64) -> Instruction {
+    build(
+        rewards_accounts(authority, mint_of_token),
+        instruction::AddRewards { amount },
+    )
+}
+
+pub fn remove_rewards(authority: &Pubkey, mint_of_token: &Pubkey, amount: u64) -> Instruction {
+    build(
+        rewards_accounts(authority, mint_of_token),
+        instruction::RemoveRewards { amount },
+    )
+}
+
+// Bonding
+
+pub fn initialize_address(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAddress {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            rewards_config: find_rewards_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAddress {},
+    )
+}
+
+pub fn migrate_address(payer: &Pubkey, address: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAddress {
+            address_bonds_rewards: find_address_bonds_rewards(address).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAddress { _address: *address },
+    )
+}
+
+// syncs the bond after the address' synced index, bond ids are synced in order
+pub fn sync_bond(address: &Pubkey, bond_id: u16) -> Instruction {
+    build(
+        accounts::SyncBond {
+            address_bonds_rewards: find_address_bonds_rewards(address).0,
+            bond: find_bond(address, bond_id).0,
+        },
+        instruction::SyncBond { _address: *address },
+    )
+}
+
+// drops the remaining lock time of an expired bond from the address liveliness
+pub fn expire_bond(address: &Pubkey, bond_id: u16) -> Instruction {
+    build(
+        accounts::ExpireBond {
+            address_bonds_rewards: find_address_bonds_rewards(address).0,
+            bond: find_bond(address, bond_id).0,
+        },
+        instruction::ExpireBond {
+            _address: *address,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+pub fn migrate_asset_usage(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    asset_id: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    nonce: u64,
+) -> Instruction {
+    build(
+        accounts::MigrateAssetUsage {
+            asset_usage: find_asset_usage(asset_id).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            bond: find_bond(owner, bond_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAssetUsage {
+            asset_id: *asset_id,
+            _bond_config_index: bond_config_index,
+            bond_id,
+            nonce,
+        },
+    )
+}
+
+pub fn migrate_rewards_config(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateRewardsConfig {
+            rewards_config: find_rewards_config().0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateRewardsConfig {},
+    )
+}
+
+pub fn update_keeper(authority: &Pubkey, keeper: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateKeeper {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            authority: *authority,
+        },
+        instruction::UpdateKeeper { keeper: *keeper },
+    )
+}
+
+// leaf data of the bonded cNFT, proof holds the merkle proof accounts
+pub struct LeafData {
+    pub merkle_tree: Pubkey,
+    pub nonce: u64,
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub leaf_delegate: Pubkey,
+    pub proof: Vec<Pubkey>,
+}
+
+pub fn bond(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    amount: u64,
+    leaf: &LeafData,
+) -> Instruction {
+    let asset_id = mpl_bubblegum::utils::get_asset_id(&leaf.merkle_tree, leaf.nonce);
+
+    let instruction = build(
+        accounts::BondContext {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            asset_usage: find_asset_usage(&asset_id).0,
+            bond: find_bond(authority, bond_id).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_sent: *mint_of_token,
+            authority: *authority,
+            merkle_tree: leaf.merkle_tree,
+            leaf_delegate: leaf.leaf_delegate,
+            asset_authority: find_asset_authority().0,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            compression_program: SplAccountCompression::id(),
+            tree_config: Some(find_tree_config(&leaf.merkle_tree).0),
+            bubblegum_program: Some(mpl_bubblegum::ID),
+            log_wrapper: Some(Noop::id()),
+        },
+        instruction::Bond {
+            _bond_config_index: bond_config_index,
+            bond_id,
+            amount,
+            nonce: leaf.nonce,
+            root: leaf.root,
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+        },
+    );
+
+    with_remaining_accounts(instruction, &leaf.proof)
+}
+
+// bond ids continue from first_bond_id, every leaf shares the given root
+pub fn bond_many(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    merkle_tree: &Pubkey,
+    bond_config_index: u8,
+    first_bond_id: u16,
+    root: [u8; 32],
+    leaves: &[LeafData],
+) -> Instruction {
+    let mut remaining_accounts = vec![];
+    let mut assets = vec![];
+
+    for (bond_id, leaf) in (first_bond_id..).zip(leaves) {
+        let asset_id = mpl_bubblegum::utils::get_asset_id(merkle_tree, leaf.nonce);
+
+        let (bond, bond_bump) = find_bond(authority, bond_id);
+        let (asset_usage, asset_usage_bump) = find_asset_usage(&asset_id);
+
+        remaining_accounts.push(bond);
+        remaining_accounts.push(asset_usage);
+        remaining_accounts.extend(&leaf.proof);
+
+        assets.push(BondManyArgs {
+            nonce: leaf.nonce,
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+            leaf_delegate: leaf.leaf_delegate,
+            proof_len: leaf.proof.len() as u8,
+            bond_bump,
+            asset_usage_bump,
+        });
+    }
+
+    let mut instruction = build(
+        accounts::BondMany {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_sent: *mint_of_token,
+            authority: *authority,
+            merkle_tree: *merkle_tree,
+            asset_authority: find_asset_authority().0,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            compression_program: SplAccountCompression::id(),
+        },
+        instruction::BondMany {
+            _bond_config_index: bond_config_index,
+            root,
+            assets,
+        },
+    );
+
+    // bonds and asset usages are created by the program
+    instruction.accounts.extend(
+        remaining_accounts
+            .into_iter()
+            .map(|account| AccountMeta::new(account, false)),
+    );
+
+    instruction
+}
+
+pub fn update_vault_bond(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    nonce: u64,
+) -> Instruction {
+    build(
+        accounts::VaultAddressUpdate {
+            address_bonds_rewards: find_address_bonds_rewards(owner).0,
+            bond: find_bond(owner, bond_id).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            owner: *owner,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateVaultBond {
+            _bond_config_index: bond_config_index,
+            bond_id,
+            nonce,
+        },
+    )
+}
+
+fn renew_accounts(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> accounts::Renew {
+    accounts::Renew {
+        bond_config: find_bond_config(bond_config_index).0,
+        program_config: find_program_config().0,
+        rewards_config: find_rewards_config().0,
+        vault_config: find_vault_config().0,
+        address_bonds_rewards: find_address_bonds_rewards(owner).0,
+        bond: find_bond(owner, bond_id).0,
+        asset_usage: find_asset_usage(asset_id).0,
+        owner: *owner,
+        authority: *authority,
+    }
+}
+
+// authority is the owner or the keeper of the owner, the bond config must be the one the
+// asset was bonded under
+pub fn renew(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> Instruction {
+    build(
+        renew_accounts(authority, owner, bond_config_index, bond_id, asset_id),
+        instruction::Renew {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+pub fn enable_auto_renew(
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> Instruction {
+    build(
+        renew_accounts(owner, owner, bond_config_index, bond_id, asset_id),
+        instruction::EnableAutoRenew {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+pub fn disable_auto_renew(
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> Instruction {
+    build(
+        renew_accounts(owner, owner, bond_config_index, bond_id, asset_id),
+        instruction::DisableAutoRenew {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+// escrowed and delegated leaves need the bond config merkle tree and the proof to be
+// returned or have the delegation revoked
+pub struct EscrowLeaf {
+    pub merkle_tree: Pubkey,
+    pub args: LeafArgs,
+    pub proof: Vec<Pubkey>,
+}
+
+fn withdraw_accounts(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    escrow_leaf: Option<&EscrowLeaf>,
+) -> accounts::Withdraw {
+    let merkle_tree = escrow_leaf.map(|leaf| leaf.merkle_tree);
+
+    accounts::Withdraw {
+        bond_config: find_bond_config(bond_config_index).0,
+        program_config: find_program_config().0,
+        address_bonds_rewards: find_address_bonds_rewards(authority).0,
+        rewards_config: find_rewards_config().0,
+        bond: find_bond(authority, bond_id).0,
+        vault_config: find_vault_config().0,
+        vault: get_vault(mint_of_token),
+        mint_of_token_to_receive: *mint_of_token,
+        authority: *authority,
+        authority_token_account: get_associated_token_address(authority, mint_of_token),
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        merkle_tree,
+        tree_config: merkle_tree.map(|merkle_tree| find_tree_config(&merkle_tree).0),
+        asset_authority: merkle_tree.map(|_| find_asset_authority().0),
+        bubblegum_program: merkle_tree.map(|_| mpl_bubblegum::ID),
+        log_wrapper: merkle_tree.map(|_| Noop::id()),
+        compression_program: merkle_tree.map(|_| SplAccountCompression::id()),
+    }
+}
+
+fn escrow_proof(escrow_leaf: Option<&EscrowLeaf>) -> &[Pubkey] {
+    escrow_leaf.map_or(&[], |leaf| &leaf.proof)
+}
+
+pub fn withdraw(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    escrow_leaf: Option<&EscrowLeaf>,
+) -> Instruction {
+    let instruction = build(
+        withdraw_accounts(
+            authority,
+            mint_of_token,
+            bond_config_index,
+            bond_id,
+            escrow_leaf,
+        ),
+        instruction::Withdraw {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+            leaf_args: escrow_leaf.map(|leaf| leaf.args),
+        },
+    );
+
+    with_remaining_accounts(instruction, escrow_proof(escrow_leaf))
+}
+
+pub fn emergency_withdraw(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    escrow_leaf: Option<&EscrowLeaf>,
+) -> Instruction {
+    let instruction = build(
+        withdraw_accounts(
+            authority,
+            mint_of_token,
+            bond_config_index,
+            bond_id,
+            escrow_leaf,
+        ),
+        instruction::EmergencyWithdraw {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+            leaf_args: escrow_leaf.map(|leaf| leaf.args),
+        },
+    );
+
+    with_remaining_accounts(instruction, escrow_proof(escrow_leaf))
+}
+
+pub fn complete_withdraw(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    escrow_leaf: Option<&EscrowLeaf>,
+) -> Instruction {
+    let instruction = build(
+        withdraw_accounts(
+            authority,
+            mint_of_token,
+            bond_config_index,
+            bond_id,
+            escrow_leaf,
+        ),
+        instruction::CompleteWithdraw {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+            leaf_args: escrow_leaf.map(|leaf| leaf.args),
+        },
+    );
+
+    with_remaining_accounts(instruction, escrow_proof(escrow_leaf))
+}
+
+fn unbond_accounts(authority: &Pubkey, bond_config_index: u8, bond_id: u16) -> accounts::Unbond {
+    accounts::Unbond {
+        bond_config: find_bond_config(bond_config_index).0,
+        program_config: find_program_config().0,
+        rewards_config: find_rewards_config().0,
+        vault_config: find_vault_config().0,
+        address_bonds_rewards: find_address_bonds_rewards(authority).0,
+        bond: find_bond(authority, bond_id).0,
+        authority: *authority,
+    }
+}
+
+pub fn start_unbond(authority: &Pubkey, bond_config_index: u8, bond_id: u16) -> Instruction {
+    build(
+        unbond_accounts(authority, bond_config_index, bond_id),
+        instruction::StartUnbond {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+pub fn cancel_unbond(authority: &Pubkey, bond_config_index: u8, bond_id: u16) -> Instruction {
+    build(
+        unbond_accounts(authority, bond_config_index, bond_id),
+        instruction::CancelUnbond {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+        },
+    )
+}
+
+pub fn partial_withdraw(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::PartialWithdraw {
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            rewards_config: find_rewards_config().0,
+            bond: find_bond(authority, bond_id).0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_to_receive: *mint_of_token,
+            authority: *authority,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::PartialWithdraw {
+            _bond_config_index: bond_config_index,
+            _bond_id: bond_id,
+            amount,
+        },
+    )
+}
+
+// target_bond_id is the bond to top up, bond_id 0 tops up the vault bond
+pub fn top_up(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    target_bond_id: u16,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::TopUp {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            rewards_config: find_rewards_config().0,
+            mint_of_token_sent: *mint_of_token,
+            bond: find_bond(authority, target_bond_id).0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            authority: *authority,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::TopUp {
+            _bond_config_index: bond_config_index,
+            bond_id,
+            amount,
+        },
+    )
+}
+
+pub fn close_bond(authority: &Pubkey, bond_id: u16, asset_id: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseBond {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            bond: find_bond(authority, bond_id).0,
+            asset_usage: find_asset_usage(asset_id).0,
+            authority: *authority,
+        },
+        instruction::CloseBond { bond_id },
+    )
+}
+
+pub fn close_address(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseAddress {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            authority: *authority,
+        },
+        instruction::CloseAddress {},
+    )
+}
+
+// Rewards
+
+// target_bond_id is the bond receiving the rewards, bond_id 0 stakes into the vault bond
+pub fn stake_rewards(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    target_bond_id: u16,
+    target_asset_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::StakeRewards {
+            address_bonds_rewards: find_address_bonds_rewards(owner).0,
+            bond: find_bond(owner, target_bond_id).0,
+            asset_usage: find_asset_usage(target_asset_id).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            program_config: find_program_config().0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            owner: *owner,
+            authority: *authority,
+        },
+        instruction::StakeRewards {
+            _bond_config_index: bond_config_index,
+            bond_id,
+        },
+    )
+}
+
+pub fn claim_rewards(
+    authority: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    target_bond_id: u16,
+) -> Instruction {
+    build(
+        accounts::ClaimRewards {
+            address_bonds_rewards: find_address_bonds_rewards(authority).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            bond: find_bond(authority, target_bond_id).0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_to_receive: *mint_of_token,
+            authority: *authority,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::ClaimRewards {
+            _bond_config_index: bond_config_index,
+            bond_id,
+        },
+    )
+}
+
+// Slashing
+
+pub fn slash_bond(authority: &Pubkey, owner: &Pubkey, bond_id: u16, reason: u8) -> Instruction {
+    build(
+        accounts::SlashBond {
+            program_config: find_program_config().0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            address_bonds_rewards: find_address_bonds_rewards(owner).0,
+            bond: find_bond(owner, bond_id).0,
+            owner: *owner,
+            authority: *authority,
+        },
+        instruction::SlashBond {
+            _bond_id: bond_id,
+            reason,
+        },
+    )
+}
+
+// authority is the bond owner or the challenger
+pub fn expire_challenge(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    challenger: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_id: u16,
+) -> Instruction {
+    let bond = find_bond(owner, bond_id).0;
+
+    build(
+        accounts::ExpireChallenge {
+            program_config: find_program_config().0,
+            challenge: find_challenge(&bond).0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_to_receive: *mint_of_token,
+            bond,
+            owner: *owner,
+            challenger: *challenger,
+            challenger_token_account: get_associated_token_address(challenger, mint_of_token),
+            authority: *authority,
+            token_program: token::ID,
+        },
+        instruction::ExpireChallenge { _bond_id: bond_id },
+    )
+}
+
+pub fn open_challenge(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    mint_of_token: &Pubkey,
+    asset_id: &Pubkey,
+    bond_id: u16,
+) -> Instruction {
+    let bond = find_bond(owner, bond_id).0;
+
+    build(
+        accounts::OpenChallenge {
+            program_config: find_program_config().0,
+            challenge: find_challenge(&bond).0,
+            bond,
+            asset_usage: find_asset_usage(asset_id).0,
+            owner: *owner,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_sent: *mint_of_token,
+            authority: *authority,
+            authority_token_account: get_associated_token_address(authority, mint_of_token),
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        instruction::OpenChallenge { _bond_id: bond_id },
+    )
+}
+
+pub fn resolve_challenge(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    challenger: &Pubkey,
+    mint_of_token: &Pubkey,
+    bond_id: u16,
+    upheld: bool,
+    reason: u8,
+) -> Instruction {
+    let bond = find_bond(owner, bond_id).0;
+
+    build(
+        accounts::ResolveChallenge {
+            program_config: find_program_config().0,
+            challenge: find_challenge(&bond).0,
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            vault: get_vault(mint_of_token),
+            mint_of_token_to_receive: *mint_of_token,
+            address_bonds_rewards: find_address_bonds_rewards(owner).0,
+            bond,
+            owner: *owner,
+            challenger: *challenger,
+            challenger_token_account: get_associated_token_address(challenger, mint_of_token),
+            authority: *authority,
+            token_program: token::ID,
+        },
+        instruction::ResolveChallenge {
+            _bond_id: bond_id,
+            upheld,
+            reason,
+        },
+    )
+}
+
+// Views, simulate these and read the return data
+
+pub fn get_claimable_rewards(address: &Pubkey) -> Instruction {
+    build(
+        accounts::ViewAddress {
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            address_bonds_rewards: find_address_bonds_rewards(address).0,
+        },
+        instruction::GetClaimableRewards { _address: *address },
+    )
+}
+
+pub fn get_address_liveliness(address: &Pubkey) -> Instruction {
+    build(
+        accounts::ViewAddress {
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+            address_bonds_rewards: find_address_bonds_rewards(address).0,
+        },
+        instruction::GetAddressLiveliness { _address: *address },
+    )
+}
+
+pub fn get_current_apr() -> Instruction {
+    build(
+        accounts::ViewRewards {
+            rewards_config: find_rewards_config().0,
+            vault_config: find_vault_config().0,
+        },
+        instruction::GetCurrentApr {},
+    )
+}
+
+pub fn get_bond_status(
+    owner: &Pubkey,
+    bond_config_index: u8,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::GetBondStatus {
+            asset_usage: find_asset_usage(asset_id).0,
+            bond: find_bond(owner, bond_id).0,
+            bond_config: find_bond_config(bond_config_index).0,
+            address_bonds_rewards: find_address_bonds_rewards(owner).0,
+        },
+        instruction::GetBondStatus {
+            asset_id: *asset_id,
+        },
+    )
+}
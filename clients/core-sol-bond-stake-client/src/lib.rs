@@ -0,0 +1,9 @@
+//! Rust client for the Itheum Bonding and Staking Program: PDA derivation, instruction builders,
+//! account deserialisers and the reward and liveliness math for quoting.
+
+pub mod accounts;
+pub mod instructions;
+pub mod math;
+pub mod pda;
+
+pub use core_sol_bond_stake_sc::{constants, errors, states, utils, BondManyArgs, BondStatus, ID};
@@ -0,0 +1,174 @@
+//! Program reward and liveliness math run on copies of the accounts, for quoting without a
+//! transaction. Amounts match what the program computes for the same slot and timestamp.
+
+use anchor_lang::Result;
+use core_sol_bond_stake_sc::{
+    constants::{MAX_PERCENT, SLOTS_IN_YEAR},
+    errors::Errors,
+    libraries::{self, full_math::MulDiv},
+    states::{AddressBondsRewards, Bond, BondConfig, RewardsConfig, VaultConfig},
+    utils::{get_unbond_timestamp, State},
+};
+
+pub use libraries::{
+    compute_address_liveliness, compute_liveliness_payout_share, get_amount_apr_bounded,
+};
+
+// rewards per share and accumulated rewards after the program distributes rewards at current_slot
+pub fn compute_aggregated_rewards(
+    rewards_config: &RewardsConfig,
+    vault_config: &VaultConfig,
+    current_slot: u64,
+) -> Result<(u128, u64)> {
+    let mut rewards_config = rewards_config.clone();
+
+    libraries::generate_aggregated_rewards(&mut rewards_config, vault_config, current_slot)?;
+
+    Ok((
+        rewards_config.rewards_per_share,
+        rewards_config.accumulated_rewards,
+    ))
+}
+
+// claimable amount before the liveliness curve is applied
+pub fn compute_claimable_rewards(
+    rewards_config: &RewardsConfig,
+    vault_config: &VaultConfig,
+    address_bonds_rewards: &AddressBondsRewards,
+    current_slot: u64,
+) -> Result<u64> {
+    let mut rewards_config = rewards_config.clone();
+    let mut address_bonds_rewards = address_bonds_rewards.clone();
+
+    libraries::accrue_address_rewards(
+        &mut rewards_config,
+        vault_config,
+        &mut address_bonds_rewards,
+        current_slot,
+    )?;
+
+    Ok(address_bonds_rewards.claimable_amount)
+}
+
+// amount claim_rewards and stake_rewards pay out at the given slot and timestamp
+pub fn quote_rewards_payout(
+    rewards_config: &RewardsConfig,
+    vault_config: &VaultConfig,
+    address_bonds_rewards: &AddressBondsRewards,
+    current_slot: u64,
+    current_timestamp: u64,
+) -> Result<u64> {
+    let mut rewards_config = rewards_config.clone();
+    let mut address_bonds_rewards = address_bonds_rewards.clone();
+
+    libraries::accrue_address_rewards(
+        &mut rewards_config,
+        vault_config,
+        &mut address_bonds_rewards,
+        current_slot,
+    )?;
+
+    libraries::apply_address_liveliness(&rewards_config, &address_bonds_rewards, current_timestamp)
+}
+
+// yearly rewards over the total bond amount, 0 - 10000 per 100%, capped by max apr
+pub fn compute_current_apr(rewards_config: &RewardsConfig, vault_config: &VaultConfig) -> u64 {
+    if rewards_config.rewards_state != State::Active.to_code()
+        || vault_config.total_bond_amount == 0
+    {
+        return 0;
+    }
+
+    let apr = rewards_config.rewards_per_slot as u128 * SLOTS_IN_YEAR as u128 * MAX_PERCENT as u128
+        / vault_config.total_bond_amount as u128;
+    let apr = core::cmp::min(apr, u64::MAX as u128) as u64;
+
+    if rewards_config.max_apr > 0 {
+        core::cmp::min(apr, rewards_config.max_apr)
+    } else {
+        apr
+    }
+}
+
+// penalty withdraw and start_unbond charge at current_timestamp, lock periods the program
+// rejects as overflowing count as never ending
+pub fn compute_withdraw_penalty(
+    bond: &Bond,
+    bond_config: &BondConfig,
+    current_timestamp: u64,
+) -> Result<u64> {
+    let unbond_timestamp = get_unbond_timestamp(
+        bond.auto_renew,
+        bond_config.lock_period,
+        current_timestamp,
+        bond.unbond_timestamp,
+    )
+    .unwrap_or(u64::MAX);
+
+    if unbond_timestamp < current_timestamp {
+        return Ok(0);
+    }
+
+    let penalty = bond
+        .bond_amount
+        .mul_div_floor(bond_config.withdraw_penalty, MAX_PERCENT)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(penalty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_sol_bond_stake_sc::{
+        constants::REWARDS_PER_SHARE_PRECISION,
+        states::fixtures::{self, liveliness_rewards_config},
+        utils::LivelinessCurve,
+    };
+
+    #[test]
+    fn payout_share_follows_curve() {
+        let linear = liveliness_rewards_config(LivelinessCurve::Linear, 0, 0);
+        assert_eq!(
+            compute_liveliness_payout_share(&linear, 95_00),
+            Ok(MAX_PERCENT)
+        );
+        assert_eq!(compute_liveliness_payout_share(&linear, 50_00), Ok(50_00));
+
+        let step = liveliness_rewards_config(LivelinessCurve::Step, 90_00, 25_00);
+        assert_eq!(
+            compute_liveliness_payout_share(&step, 90_00),
+            Ok(MAX_PERCENT)
+        );
+        assert_eq!(compute_liveliness_payout_share(&step, 89_99), Ok(25_00));
+
+        let quadratic = liveliness_rewards_config(LivelinessCurve::QuadraticDecay, 80_00, 0);
+        assert_eq!(compute_liveliness_payout_share(&quadratic, 0), Ok(0));
+        assert_eq!(
+            compute_liveliness_payout_share(&quadratic, 40_00),
+            Ok(75_00)
+        );
+    }
+
+    #[test]
+    fn aggregated_rewards_stop_without_reserve() {
+        let rewards_config = RewardsConfig {
+            rewards_per_slot: 100,
+            rewards_reserve: 1_000,
+            ..fixtures::rewards_config()
+        };
+        let vault_config = VaultConfig {
+            total_bond_amount: 1_000,
+            ..fixtures::vault_config()
+        };
+
+        assert_eq!(
+            compute_aggregated_rewards(&rewards_config, &vault_config, 10),
+            Ok((REWARDS_PER_SHARE_PRECISION, 1_000))
+        );
+        assert_eq!(
+            compute_aggregated_rewards(&rewards_config, &vault_config, 11),
+            Ok((0, 0))
+        );
+    }
+}
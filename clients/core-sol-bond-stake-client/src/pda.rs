@@ -0,0 +1,61 @@
+use anchor_spl::associated_token::get_associated_token_address;
+use core_sol_bond_stake_sc::{
+    constants::{
+        ADDRESS_BONDS_REWARDS_SEED, ASSET_AUTHORITY_SEED, BOND_CONFIG_SEED, BOND_SEED,
+        CHALLENGE_SEED, PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+    },
+    ID,
+};
+use solana_program::pubkey::Pubkey;
+
+pub fn find_bond_config(index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_CONFIG_SEED.as_bytes(), &index.to_be_bytes()], &ID)
+}
+
+pub fn find_rewards_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARDS_CONFIG_SEED.as_bytes()], &ID)
+}
+
+pub fn find_vault_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_CONFIG_SEED.as_bytes()], &ID)
+}
+
+pub fn find_program_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED.as_bytes()], &ID)
+}
+
+pub fn find_asset_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSET_AUTHORITY_SEED.as_bytes()], &ID)
+}
+
+pub fn find_address_bonds_rewards(address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), address.as_ref()],
+        &ID,
+    )
+}
+
+// bond ids are little endian, bond config indexes big endian
+pub fn find_bond(owner: &Pubkey, bond_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BOND_SEED.as_bytes(), owner.as_ref(), &bond_id.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn find_asset_usage(asset_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[asset_id.as_ref()], &ID)
+}
+
+pub fn find_challenge(bond: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHALLENGE_SEED.as_bytes(), bond.as_ref()], &ID)
+}
+
+// token account of the vault config holding bonds and rewards
+pub fn get_vault(mint_of_token: &Pubkey) -> Pubkey {
+    get_associated_token_address(&find_vault_config().0, mint_of_token)
+}
+
+pub fn find_tree_config(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &mpl_bubblegum::ID)
+}
@@ -0,0 +1,1984 @@
+//! Bond lifecycle against an in-process bank, bubblegum and account compression run from the
+//! programs bundled in `tests/`. Configs are written directly since the admin key is fixed.
+
+use anchor_lang::{
+    AccountSerialize, AnchorDeserialize, Id, InstructionData, Space, ToAccountMetas,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use core_sol_bond_stake_client::{
+    accounts,
+    constants::{DIVISION_SAFETY_CONST, MAX_PERCENT, REWARDS_PER_SHARE_PRECISION},
+    errors::Errors,
+    instructions,
+    instructions::{EscrowLeaf, LeafData},
+    math, pda,
+    states::{AddressBondsRewards, Bond, BondConfig, ProgramConfig, RewardsConfig, VaultConfig},
+    utils::{AssetMode, LeafArgs, LivelinessCurve, PauseFlag, SlashDestination, State},
+    BondStatus, ID,
+};
+use mpl_bubblegum::{
+    hash::{hash_creators, hash_metadata},
+    instructions::{CreateTreeConfigBuilder, MintV1Builder},
+    types::{LeafSchema, MetadataArgs, TokenProgramVersion, TokenStandard},
+    utils::get_asset_id,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, keccak, program_pack::Pack,
+    pubkey::Pubkey, rent::Rent, system_instruction,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_account_compression::{
+    state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, ConcurrentMerkleTree, Noop,
+};
+
+const MAX_DEPTH: usize = 3;
+const MAX_BUFFER_SIZE: usize = 8;
+
+const BOND_CONFIG_INDEX: u8 = 1;
+// same tree, ten times the lock period
+const LONG_BOND_CONFIG_INDEX: u8 = 2;
+const BOND_AMOUNT: u64 = 1_000_000;
+const LOCK_PERIOD: u64 = 1_000;
+const WITHDRAW_PENALTY: u64 = 50_00;
+const REWARDS_PER_SLOT: u64 = 1_000;
+const REWARDS_RESERVE: u64 = 1_000_000_000;
+const USER_BALANCE: u64 = 10_000_000;
+const CHALLENGE_AMOUNT: u64 = 1_000_000;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // anchor ties the account slice to the account info lifetime
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    core_sol_bond_stake_sc::entry(program_id, accounts, data)
+}
+
+struct Setup {
+    context: ProgramTestContext,
+    user: Keypair,
+    challenger: Keypair,
+    mint: Pubkey,
+    merkle_tree: Keypair,
+}
+
+fn anchor_account<T: AccountSerialize + Space>(account: &T) -> Account {
+    let mut data = Vec::with_capacity(T::INIT_SPACE);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(T::INIT_SPACE, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    }
+}
+
+// the account as written before rewards per share was widened from a u64 to a u128
+fn legacy_account<T: AccountSerialize + Space>(account: &T, offset: usize, legacy: u64) -> Account {
+    let mut data = anchor_account(account).data;
+    data.splice(offset..offset + 16, legacy.to_le_bytes());
+    data.truncate(T::INIT_SPACE - 8);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: ID,
+        ..Account::default()
+    }
+}
+
+fn packed_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        ..Account::default()
+    }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    packed_account(spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    })
+}
+
+async fn setup() -> Setup {
+    std::env::set_var(
+        "SBF_OUT_DIR",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../../tests"),
+    );
+
+    // the program runs natively, the bundled programs are loaded from SBF_OUT_DIR
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "core_sol_bond_stake_sc",
+        ID,
+        processor!(process_instruction),
+    );
+    program_test.prefer_bpf(true);
+    program_test.add_program("bubble_gum_program", mpl_bubblegum::ID, None);
+    program_test.add_program("compression", spl_account_compression::id(), None);
+    program_test.add_program("logWrapper", Noop::id(), None);
+
+    let user = Keypair::new();
+    let challenger = Keypair::new();
+    let mint = Pubkey::new_unique();
+    let merkle_tree = Keypair::new();
+    let (vault_config, vault_config_bump) = pda::find_vault_config();
+    let vault = pda::get_vault(&mint);
+
+    program_test.add_account(
+        user.pubkey(),
+        Account::new(10_000_000_000, 0, &solana_program::system_program::ID),
+    );
+    program_test.add_account(
+        mint,
+        packed_account(spl_token::state::Mint {
+            supply: USER_BALANCE + CHALLENGE_AMOUNT + REWARDS_RESERVE,
+            decimals: 9,
+            is_initialized: true,
+            ..Default::default()
+        }),
+    );
+    program_test.add_account(
+        get_associated_token_address(&user.pubkey(), &mint),
+        token_account(&mint, &user.pubkey(), USER_BALANCE),
+    );
+    program_test.add_account(
+        challenger.pubkey(),
+        Account::new(10_000_000_000, 0, &solana_program::system_program::ID),
+    );
+    program_test.add_account(
+        get_associated_token_address(&challenger.pubkey(), &mint),
+        token_account(&mint, &challenger.pubkey(), CHALLENGE_AMOUNT),
+    );
+    program_test.add_account(vault, token_account(&mint, &vault_config, REWARDS_RESERVE));
+
+    for (index, lock_period) in [
+        (BOND_CONFIG_INDEX, LOCK_PERIOD),
+        (LONG_BOND_CONFIG_INDEX, 10 * LOCK_PERIOD),
+    ] {
+        program_test.add_account(
+            pda::find_bond_config(index).0,
+            anchor_account(&BondConfig {
+                bump: pda::find_bond_config(index).1,
+                index,
+                bond_state: State::Active.to_code(),
+                merkle_tree: merkle_tree.pubkey(),
+                lock_period,
+                bond_amount: BOND_AMOUNT,
+                withdraw_penalty: WITHDRAW_PENALTY,
+                asset_mode: AssetMode::Unrestricted.to_code(),
+                cooldown_period: 0,
+                pause_flags: 0,
+                padding: [0; 22],
+            }),
+        );
+    }
+    program_test.add_account(
+        pda::find_rewards_config().0,
+        anchor_account(&RewardsConfig {
+            bump: pda::find_rewards_config().1,
+            rewards_state: State::Active.to_code(),
+            rewards_reserve: REWARDS_RESERVE,
+            accumulated_rewards: 0,
+            rewards_per_slot: REWARDS_PER_SLOT,
+            rewards_per_share: 0,
+            last_reward_slot: 0,
+            max_apr: 0,
+            liveliness_curve: LivelinessCurve::Linear.to_code(),
+            liveliness_threshold: 95_00,
+            liveliness_curve_param: 0,
+            padding: [0; 15],
+        }),
+    );
+    program_test.add_account(
+        vault_config,
+        anchor_account(&VaultConfig {
+            bump: vault_config_bump,
+            vault,
+            mint_of_token: mint,
+            total_bond_amount: 0,
+            total_penalized_amount: 0,
+            total_challenge_amount: 0,
+            padding: [0; 24],
+        }),
+    );
+    program_test.add_account(
+        pda::find_program_config().0,
+        anchor_account(&ProgramConfig {
+            bump: pda::find_program_config().1,
+            slashing_authority: Pubkey::new_unique(),
+            slash_share: 0,
+            slash_destination: SlashDestination::Treasury.to_code(),
+            arbiter: Pubkey::new_unique(),
+            challenge_amount: 0,
+            emergency_state: State::Inactive.to_code(),
+            pause_flags: 0,
+            challenge_period: 0,
+            padding: [0; 14],
+        }),
+    );
+
+    let mut setup = Setup {
+        context: program_test.start_with_context().await,
+        user,
+        challenger,
+        mint,
+        merkle_tree,
+    };
+
+    create_tree(&mut setup).await;
+
+    let user = setup.user.insecure_clone();
+    process(
+        &mut setup.context,
+        &[instructions::initialize_address(&user.pubkey())],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    setup
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_program_error(result: Result<(), BanksClientError>, error: Errors) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
+
+async fn create_tree(setup: &mut Setup) {
+    let payer = setup.context.payer.pubkey();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let space = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1
+        + std::mem::size_of::<ConcurrentMerkleTree<MAX_DEPTH, MAX_BUFFER_SIZE>>();
+
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &merkle_tree,
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &spl_account_compression::id(),
+        ),
+        CreateTreeConfigBuilder::new()
+            .tree_config(pda::find_tree_config(&merkle_tree).0)
+            .merkle_tree(merkle_tree)
+            .payer(payer)
+            .tree_creator(payer)
+            .max_depth(MAX_DEPTH as u32)
+            .max_buffer_size(MAX_BUFFER_SIZE as u32)
+            .instruction(),
+    ];
+
+    let merkle_tree = setup.merkle_tree.insecure_clone();
+    process(&mut setup.context, &instructions, &[&merkle_tree])
+        .await
+        .unwrap();
+}
+
+fn metadata() -> MetadataArgs {
+    MetadataArgs {
+        name: "Data NFT".to_string(),
+        symbol: "DNFT".to_string(),
+        uri: "https://api.itheum.io/".to_string(),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![],
+    }
+}
+
+// mints the first leaf of the tree to the user and returns its leaf data and proof
+async fn mint_cnft(setup: &mut Setup) -> LeafData {
+    let payer = setup.context.payer.pubkey();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let user = setup.user.pubkey();
+
+    let instruction = MintV1Builder::new()
+        .tree_config(pda::find_tree_config(&merkle_tree).0)
+        .leaf_owner(user)
+        .leaf_delegate(user)
+        .merkle_tree(merkle_tree)
+        .payer(payer)
+        .tree_creator_or_delegate(payer)
+        .metadata(metadata())
+        .instruction();
+
+    process(&mut setup.context, &[instruction], &[])
+        .await
+        .unwrap();
+
+    leaf_data(&merkle_tree, &user, &user)
+}
+
+// the first leaf of the tree as it hashes for owner and delegate
+fn leaf_data(merkle_tree: &Pubkey, owner: &Pubkey, delegate: &Pubkey) -> LeafData {
+    let nonce = 0;
+    let data_hash = hash_metadata(&metadata()).unwrap();
+    let creator_hash = hash_creators(&[]);
+    let leaf = LeafSchema::V1 {
+        id: get_asset_id(merkle_tree, nonce),
+        owner: *owner,
+        delegate: *delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+    };
+
+    // the only leaf sits at index 0, every sibling is an empty subtree
+    let mut node = leaf.hash();
+    let mut empty = [0; 32];
+    let mut proof = vec![];
+
+    for _ in 0..MAX_DEPTH {
+        proof.push(Pubkey::new_from_array(empty));
+        node = keccak::hashv(&[&node, &empty]).to_bytes();
+        empty = keccak::hashv(&[&empty, &empty]).to_bytes();
+    }
+
+    LeafData {
+        merkle_tree: *merkle_tree,
+        nonce,
+        root: node,
+        data_hash,
+        creator_hash,
+        leaf_delegate: *delegate,
+        proof,
+    }
+}
+
+fn escrow_leaf(leaf: &LeafData) -> EscrowLeaf {
+    EscrowLeaf {
+        merkle_tree: leaf.merkle_tree,
+        args: LeafArgs {
+            nonce: leaf.nonce,
+            root: leaf.root,
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+        },
+        proof: leaf.proof.clone(),
+    }
+}
+
+// succeeds only while the tree holds the first leaf with this owner and delegate
+async fn verify_leaf(
+    context: &mut ProgramTestContext,
+    merkle_tree: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+) -> Result<(), BanksClientError> {
+    let leaf = leaf_data(merkle_tree, owner, delegate);
+    let hash = LeafSchema::V1 {
+        id: get_asset_id(merkle_tree, leaf.nonce),
+        owner: *owner,
+        delegate: *delegate,
+        nonce: leaf.nonce,
+        data_hash: leaf.data_hash,
+        creator_hash: leaf.creator_hash,
+    }
+    .hash();
+
+    let mut accounts = spl_account_compression::accounts::VerifyLeaf {
+        merkle_tree: *merkle_tree,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        leaf.proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+
+    let instruction = Instruction {
+        program_id: spl_account_compression::id(),
+        accounts,
+        data: spl_account_compression::instruction::VerifyLeaf {
+            root: leaf.root,
+            leaf: hash,
+            index: leaf.nonce as u32,
+        }
+        .data(),
+    };
+
+    process(context, &[instruction], &[]).await
+}
+
+// configs are seeded directly, there is no admin key to update them through instructions
+async fn update_bond_config(
+    context: &mut ProgramTestContext,
+    update: impl FnOnce(&mut BondConfig),
+) {
+    let address = pda::find_bond_config(BOND_CONFIG_INDEX).0;
+    let mut bond_config =
+        accounts::deserialize_bond_config(&account_data(context, &address).await).unwrap();
+    update(&mut bond_config);
+
+    context.set_account(&address, &anchor_account(&bond_config).into());
+}
+
+async fn update_program_config(
+    context: &mut ProgramTestContext,
+    update: impl FnOnce(&mut ProgramConfig),
+) {
+    let address = pda::find_program_config().0;
+    let mut program_config =
+        accounts::deserialize_program_config(&account_data(context, &address).await).unwrap();
+    update(&mut program_config);
+
+    context.set_account(&address, &anchor_account(&program_config).into());
+}
+
+async fn bond(setup: &mut Setup) -> LeafData {
+    let leaf = mint_cnft(setup).await;
+    let user = setup.user.insecure_clone();
+
+    process(
+        &mut setup.context,
+        &[instructions::bond(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            BOND_AMOUNT,
+            &leaf,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    leaf
+}
+
+// advances the bank by slots and the clock by seconds
+async fn warp(context: &mut ProgramTestContext, slots: u64, seconds: i64) {
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    context.warp_to_slot(clock.slot + slots).unwrap();
+
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+async fn clock(context: &mut ProgramTestContext) -> Clock {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap()
+}
+
+async fn account_data(context: &mut ProgramTestContext, address: &Pubkey) -> Vec<u8> {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+}
+
+async fn bond_account(context: &mut ProgramTestContext, owner: &Pubkey, bond_id: u16) -> Bond {
+    let data = account_data(context, &pda::find_bond(owner, bond_id).0).await;
+    accounts::deserialize_bond(&data).unwrap()
+}
+
+async fn address_bonds_rewards(
+    context: &mut ProgramTestContext,
+    owner: &Pubkey,
+) -> AddressBondsRewards {
+    let data = account_data(context, &pda::find_address_bonds_rewards(owner).0).await;
+    accounts::deserialize_address_bonds_rewards(&data).unwrap()
+}
+
+async fn rewards_config(context: &mut ProgramTestContext) -> RewardsConfig {
+    let data = account_data(context, &pda::find_rewards_config().0).await;
+    accounts::deserialize_rewards_config(&data).unwrap()
+}
+
+async fn vault_config(context: &mut ProgramTestContext) -> VaultConfig {
+    let data = account_data(context, &pda::find_vault_config().0).await;
+    accounts::deserialize_vault_config(&data).unwrap()
+}
+
+// forces vault totals the seeded accounts cannot reach through instructions
+async fn set_total_bond_amount(context: &mut ProgramTestContext, total_bond_amount: u64) {
+    let mut vault_config = vault_config(context).await;
+    vault_config.total_bond_amount = total_bond_amount;
+
+    context.set_account(
+        &pda::find_vault_config().0,
+        &anchor_account(&vault_config).into(),
+    );
+}
+
+async fn token_balance(context: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    let data = account_data(context, &get_associated_token_address(owner, mint)).await;
+    spl_token::state::Account::unpack(&data).unwrap().amount
+}
+
+async fn liveliness(context: &mut ProgramTestContext, owner: &Pubkey) -> u64 {
+    let address_bonds_rewards = address_bonds_rewards(context, owner).await;
+    let timestamp = clock(context).await.unix_timestamp as u64;
+
+    math::compute_address_liveliness(&address_bonds_rewards, timestamp).unwrap()
+}
+
+// simulates the view and decodes its return data
+async fn bond_status(
+    context: &mut ProgramTestContext,
+    owner: &Pubkey,
+    bond_id: u16,
+    asset_id: &Pubkey,
+) -> BondStatus {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instructions::get_bond_status(
+            owner,
+            BOND_CONFIG_INDEX,
+            bond_id,
+            asset_id,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+    simulation.result.unwrap().unwrap();
+
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    BondStatus::try_from_slice(&return_data.data).unwrap()
+}
+
+#[tokio::test]
+async fn bond_escrows_tokens_and_records_asset_usage() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.pubkey();
+    let context = &mut setup.context;
+
+    let bond = bond_account(context, &user, 1).await;
+    assert_eq!(bond.state, State::Active.to_code());
+    assert_eq!(bond.bond_amount, BOND_AMOUNT);
+    assert_eq!(bond.unbond_timestamp, bond.bond_timestamp + LOCK_PERIOD);
+
+    let asset_id = get_asset_id(&leaf.merkle_tree, leaf.nonce);
+    assert_eq!(bond.asset_id, asset_id);
+
+    let data = account_data(context, &pda::find_asset_usage(&asset_id).0).await;
+    let asset_usage = accounts::deserialize_asset_usage(&data).unwrap();
+    assert_eq!(asset_usage.bond, pda::find_bond(&user, 1).0);
+    assert_eq!(asset_usage.owner, user);
+    assert_eq!(asset_usage.bond_config_index, BOND_CONFIG_INDEX);
+
+    assert_eq!(
+        token_balance(context, &user, &setup.mint).await,
+        USER_BALANCE - BOND_AMOUNT
+    );
+    assert_eq!(vault_config(context).await.total_bond_amount, BOND_AMOUNT);
+    assert_eq!(liveliness(context, &user).await, MAX_PERCENT);
+
+    // the same leaf cannot back a second bond
+    let user = setup.user.insecure_clone();
+    let result = process(
+        context,
+        &[instructions::bond(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            2,
+            BOND_AMOUNT,
+            &leaf,
+        )],
+        &[&user],
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn bond_many_creates_prefunded_accounts() {
+    let mut setup = setup().await;
+    let leaf = mint_cnft(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let payer = setup.context.payer.pubkey();
+    let context = &mut setup.context;
+
+    // anyone can fund the next bond and asset usage addresses ahead of the bond
+    let asset_id = get_asset_id(&leaf.merkle_tree, leaf.nonce);
+    let bond = pda::find_bond(&user.pubkey(), 1).0;
+    let asset_usage = pda::find_asset_usage(&asset_id).0;
+    let lamports = Rent::default().minimum_balance(0);
+    process(
+        context,
+        &[
+            system_instruction::transfer(&payer, &bond, lamports),
+            system_instruction::transfer(&payer, &asset_usage, lamports),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    process(
+        context,
+        &[instructions::bond_many(
+            &user.pubkey(),
+            &setup.mint,
+            &leaf.merkle_tree,
+            BOND_CONFIG_INDEX,
+            1,
+            leaf.root,
+            std::slice::from_ref(&leaf),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.state, State::Active.to_code());
+    assert_eq!(bond.asset_id, asset_id);
+
+    let data = account_data(context, &asset_usage).await;
+    let asset_usage = accounts::deserialize_asset_usage(&data).unwrap();
+    assert_eq!(asset_usage.bond, pda::find_bond(&user.pubkey(), 1).0);
+    assert_eq!(vault_config(context).await.total_bond_amount, BOND_AMOUNT);
+}
+
+#[tokio::test]
+async fn bond_many_rejects_a_non_canonical_bond_bump() {
+    let mut setup = setup().await;
+    let leaf = mint_cnft(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    let (bond, canonical_bump) = pda::find_bond(&user.pubkey(), 1);
+    let (other_bond, other_bump) = (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            Pubkey::create_program_address(
+                &[
+                    b"bond",
+                    user.pubkey().as_ref(),
+                    &1u16.to_le_bytes(),
+                    &[bump],
+                ],
+                &ID,
+            )
+            .ok()
+            .map(|address| (address, bump))
+        })
+        .unwrap();
+
+    let mut instruction = instructions::bond_many(
+        &user.pubkey(),
+        &setup.mint,
+        &leaf.merkle_tree,
+        BOND_CONFIG_INDEX,
+        1,
+        leaf.root,
+        std::slice::from_ref(&leaf),
+    );
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == bond {
+            account.pubkey = other_bond;
+        }
+    }
+    // the bond bump is the second to last byte of the only asset
+    let len = instruction.data.len();
+    instruction.data[len - 2] = other_bump;
+
+    let result = process(context, &[instruction], &[&user]).await;
+    assert_program_error(result, Errors::InvalidRemainingAccounts);
+}
+
+#[tokio::test]
+async fn withdraw_revokes_delegate_mode_delegation() {
+    let mut setup = setup().await;
+    update_bond_config(&mut setup.context, |bond_config| {
+        bond_config.asset_mode = AssetMode::Delegate.to_code()
+    })
+    .await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let asset_authority = pda::find_asset_authority().0;
+    let context = &mut setup.context;
+
+    verify_leaf(context, &merkle_tree, &user.pubkey(), &asset_authority)
+        .await
+        .unwrap();
+    assert!(
+        verify_leaf(context, &merkle_tree, &user.pubkey(), &user.pubkey())
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        bond_account(context, &user.pubkey(), 1).await.asset_mode,
+        AssetMode::Delegate.to_code()
+    );
+
+    let delegated = leaf_data(&merkle_tree, &user.pubkey(), &asset_authority);
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            Some(&escrow_leaf(&delegated)),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    verify_leaf(context, &merkle_tree, &user.pubkey(), &user.pubkey())
+        .await
+        .unwrap();
+}
+
+// bonds a leaf in escrow mode and checks the tree now holds it for the asset authority
+async fn bond_escrowed(setup: &mut Setup) -> LeafData {
+    update_bond_config(&mut setup.context, |bond_config| {
+        bond_config.asset_mode = AssetMode::Escrow.to_code()
+    })
+    .await;
+    bond(setup).await;
+
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let asset_authority = pda::find_asset_authority().0;
+
+    verify_leaf(
+        &mut setup.context,
+        &merkle_tree,
+        &asset_authority,
+        &asset_authority,
+    )
+    .await
+    .unwrap();
+
+    leaf_data(&merkle_tree, &asset_authority, &asset_authority)
+}
+
+#[tokio::test]
+async fn escrow_bond_requires_bubblegum_accounts() {
+    let mut setup = setup().await;
+    update_bond_config(&mut setup.context, |bond_config| {
+        bond_config.asset_mode = AssetMode::Escrow.to_code()
+    })
+    .await;
+    let leaf = mint_cnft(&mut setup).await;
+    let user = setup.user.insecure_clone();
+
+    let mut instruction = instructions::bond(
+        &user.pubkey(),
+        &setup.mint,
+        BOND_CONFIG_INDEX,
+        1,
+        BOND_AMOUNT,
+        &leaf,
+    );
+
+    // tree_config, bubblegum_program and log_wrapper precede the proof, the program id marks None
+    let optional = instruction.accounts.len() - leaf.proof.len() - 3;
+    for account in &mut instruction.accounts[optional..optional + 3] {
+        *account = AccountMeta::new_readonly(ID, false);
+    }
+
+    let result = process(&mut setup.context, &[instruction], &[&user]).await;
+    assert_program_error(result, Errors::MissingAccounts);
+}
+
+#[tokio::test]
+async fn escrowed_leaf_returns_on_withdraw() {
+    let mut setup = setup().await;
+    let escrowed = bond_escrowed(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let context = &mut setup.context;
+
+    let result = process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::MissingAccounts);
+
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            Some(&escrow_leaf(&escrowed)),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    verify_leaf(context, &merkle_tree, &user.pubkey(), &user.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        bond_account(context, &user.pubkey(), 1).await.state,
+        State::Inactive.to_code()
+    );
+}
+
+#[tokio::test]
+async fn escrowed_leaf_returns_on_complete_withdraw() {
+    let mut setup = setup().await;
+    update_bond_config(&mut setup.context, |bond_config| {
+        bond_config.cooldown_period = 100
+    })
+    .await;
+    let escrowed = bond_escrowed(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let context = &mut setup.context;
+
+    process(
+        context,
+        &[instructions::start_unbond(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // unbonding keeps the leaf in escrow
+    verify_leaf(
+        context,
+        &merkle_tree,
+        &pda::find_asset_authority().0,
+        &pda::find_asset_authority().0,
+    )
+    .await
+    .unwrap();
+
+    warp(context, 10, 100).await;
+
+    let result = process(
+        context,
+        &[instructions::complete_withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::MissingAccounts);
+
+    process(
+        context,
+        &[instructions::complete_withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            Some(&escrow_leaf(&escrowed)),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    verify_leaf(context, &merkle_tree, &user.pubkey(), &user.pubkey())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn escrowed_leaf_returns_on_emergency_withdraw() {
+    let mut setup = setup().await;
+    let escrowed = bond_escrowed(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let merkle_tree = setup.merkle_tree.pubkey();
+    let context = &mut setup.context;
+
+    update_program_config(context, |program_config| {
+        program_config.emergency_state = State::Active.to_code()
+    })
+    .await;
+
+    let result = process(
+        context,
+        &[instructions::emergency_withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::MissingAccounts);
+
+    process(
+        context,
+        &[instructions::emergency_withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            Some(&escrow_leaf(&escrowed)),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    verify_leaf(context, &merkle_tree, &user.pubkey(), &user.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        token_balance(context, &user.pubkey(), &setup.mint).await,
+        USER_BALANCE
+    );
+}
+
+#[tokio::test]
+async fn open_challenge_expires_after_challenge_period() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let challenger = setup.challenger.insecure_clone();
+    let context = &mut setup.context;
+
+    update_program_config(context, |program_config| {
+        program_config.challenge_amount = CHALLENGE_AMOUNT;
+        program_config.challenge_period = 100;
+    })
+    .await;
+
+    process(
+        context,
+        &[instructions::open_challenge(
+            &challenger.pubkey(),
+            &user.pubkey(),
+            &setup.mint,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+            1,
+        )],
+        &[&challenger],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        vault_config(context).await.total_challenge_amount,
+        CHALLENGE_AMOUNT
+    );
+    assert_eq!(bond_account(context, &user.pubkey(), 1).await.challenged, 1);
+
+    // the counter-bond cannot pay out rewards
+    let vault = get_associated_token_address(&pda::find_vault_config().0, &setup.mint);
+    context.set_account(
+        &vault,
+        &token_account(&setup.mint, &pda::find_vault_config().0, CHALLENGE_AMOUNT).into(),
+    );
+    warp(context, 10, 0).await;
+
+    let result = process(
+        context,
+        &[instructions::claim_rewards(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            1,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::NotEnoughBalance);
+
+    let expire_challenge = |authority: &Pubkey| {
+        instructions::expire_challenge(
+            authority,
+            &user.pubkey(),
+            &challenger.pubkey(),
+            &setup.mint,
+            1,
+        )
+    };
+
+    let result = process(context, &[expire_challenge(&user.pubkey())], &[&user]).await;
+    assert_program_error(result, Errors::ChallengeNotExpired);
+
+    warp(context, 10, 100).await;
+
+    let payer = context.payer.pubkey();
+    let result = process(context, &[expire_challenge(&payer)], &[]).await;
+    assert_program_error(result, Errors::NotPrivileged);
+
+    process(context, &[expire_challenge(&user.pubkey())], &[&user])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_balance(context, &challenger.pubkey(), &setup.mint).await,
+        CHALLENGE_AMOUNT
+    );
+    assert_eq!(vault_config(context).await.total_challenge_amount, 0);
+    assert_eq!(bond_account(context, &user.pubkey(), 1).await.challenged, 0);
+
+    let bond = pda::find_bond(&user.pubkey(), 1).0;
+    assert!(context
+        .banks_client
+        .get_account(pda::find_challenge(&bond).0)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+// rewinds the address and bond to how accounts created before open bond and liveliness
+// tracking read
+async fn set_unsynced(context: &mut ProgramTestContext, owner: &Pubkey, bond_id: u16) {
+    let mut address_bonds_rewards = address_bonds_rewards(context, owner).await;
+    address_bonds_rewards.open_bonds = 0;
+    address_bonds_rewards.synced_index = 0;
+    address_bonds_rewards.liveliness_unbond_sum = 0;
+    address_bonds_rewards.liveliness_amount = 0;
+    address_bonds_rewards.liveliness_auto_renew_lock_sum = 0;
+    address_bonds_rewards.liveliness_lock_sum = 0;
+    context.set_account(
+        &pda::find_address_bonds_rewards(owner).0,
+        &anchor_account(&address_bonds_rewards).into(),
+    );
+
+    let mut bond = bond_account(context, owner, bond_id).await;
+    bond.counted = 0;
+    context.set_account(
+        &pda::find_bond(owner, bond_id).0,
+        &anchor_account(&bond).into(),
+    );
+}
+
+#[tokio::test]
+async fn sync_adds_legacy_bond_liveliness() {
+    let mut setup = setup().await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    set_unsynced(context, &user.pubkey(), 1).await;
+
+    // the sums miss the legacy bond, rewards wait for the sync
+    warp(context, 10, (LOCK_PERIOD / 2) as i64).await;
+    let result = process(
+        context,
+        &[instructions::claim_rewards(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            1,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::AddressNotSynced);
+
+    process(context, &[instructions::sync_bond(&user.pubkey(), 1)], &[])
+        .await
+        .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    let address_bonds_rewards = address_bonds_rewards(context, &user.pubkey()).await;
+    assert_eq!(address_bonds_rewards.liveliness_amount, BOND_AMOUNT);
+    assert_eq!(
+        address_bonds_rewards.liveliness_unbond_sum,
+        BOND_AMOUNT as u128 * bond.unbond_timestamp as u128
+    );
+    assert_eq!(
+        address_bonds_rewards.liveliness_lock_sum,
+        BOND_AMOUNT as u128 * LOCK_PERIOD as u128
+    );
+    assert_eq!(liveliness(context, &user.pubkey()).await, MAX_PERCENT / 2);
+}
+
+#[tokio::test]
+async fn unsynced_bonds_block_closing_the_address() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    set_unsynced(context, &user.pubkey(), 1).await;
+
+    warp(context, 10, LOCK_PERIOD as i64).await;
+
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // the bond account still exists, the address cannot be closed and reinitialized
+    let result = process(
+        context,
+        &[instructions::close_address(&user.pubkey())],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::AddressHasOpenBonds);
+
+    process(context, &[instructions::sync_bond(&user.pubkey(), 1)], &[])
+        .await
+        .unwrap();
+
+    let address_bonds_rewards = address_bonds_rewards(context, &user.pubkey()).await;
+    assert_eq!(address_bonds_rewards.open_bonds, 1);
+    assert_eq!(address_bonds_rewards.synced_index, 1);
+    assert_eq!(bond_account(context, &user.pubkey(), 1).await.counted, 1);
+
+    let result = process(context, &[instructions::sync_bond(&user.pubkey(), 2)], &[]).await;
+    assert_program_error(result, Errors::AlreadyMigrated);
+
+    let result = process(
+        context,
+        &[instructions::close_address(&user.pubkey())],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::AddressHasOpenBonds);
+
+    process(
+        context,
+        &[instructions::close_bond(
+            &user.pubkey(),
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // rewards earned before the withdraw are the user's, the address stays open until claimed
+    assert!(address_bonds_rewards.claimable_amount > 0);
+
+    let result = process(
+        context,
+        &[instructions::close_address(&user.pubkey())],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::UnclaimedRewards);
+}
+
+#[tokio::test]
+async fn renew_restores_liveliness() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    warp(context, 10, (LOCK_PERIOD / 2) as i64).await;
+    assert_eq!(liveliness(context, &user.pubkey()).await, 50_00);
+
+    process(
+        context,
+        &[instructions::renew(
+            &user.pubkey(),
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let timestamp = clock(context).await.unix_timestamp as u64;
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.unbond_timestamp, timestamp + LOCK_PERIOD);
+    assert_eq!(liveliness(context, &user.pubkey()).await, MAX_PERCENT);
+
+    warp(context, 10, LOCK_PERIOD as i64).await;
+    assert_eq!(liveliness(context, &user.pubkey()).await, 0);
+}
+
+#[tokio::test]
+async fn keeper_renews_only_under_the_bond_config() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let keeper = Keypair::new();
+    let context = &mut setup.context;
+
+    process(
+        context,
+        &[instructions::update_keeper(
+            &user.pubkey(),
+            &keeper.pubkey(),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // a longer tier would extend the owner's lock past what they bonded for
+    let result = process(
+        context,
+        &[instructions::renew(
+            &keeper.pubkey(),
+            &user.pubkey(),
+            LONG_BOND_CONFIG_INDEX,
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&keeper],
+    )
+    .await;
+    assert_program_error(result, Errors::BondConfigMismatch);
+
+    warp(context, 10, 10).await;
+    process(
+        context,
+        &[instructions::renew(
+            &keeper.pubkey(),
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&keeper],
+    )
+    .await
+    .unwrap();
+
+    let timestamp = clock(context).await.unix_timestamp as u64;
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.unbond_timestamp, timestamp + LOCK_PERIOD);
+}
+
+#[tokio::test]
+async fn expire_bond_drops_remaining_lock_time() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    warp(context, 10, LOCK_PERIOD as i64 + 1).await;
+
+    // permissionless, the payer is not the bond owner
+    process(
+        context,
+        &[instructions::expire_bond(&user.pubkey(), 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    let address_bonds_rewards = address_bonds_rewards(context, &user.pubkey()).await;
+    assert_eq!(bond.lock_expired, 1);
+    assert_eq!(address_bonds_rewards.liveliness_unbond_sum, 0);
+    assert_eq!(address_bonds_rewards.liveliness_amount, 0);
+    assert_eq!(
+        address_bonds_rewards.liveliness_lock_sum,
+        BOND_AMOUNT as u128 * LOCK_PERIOD as u128
+    );
+    assert_eq!(liveliness(context, &user.pubkey()).await, 0);
+
+    // renewing brings the remaining lock time back
+    process(
+        context,
+        &[instructions::renew(
+            &user.pubkey(),
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.lock_expired, 0);
+    assert_eq!(liveliness(context, &user.pubkey()).await, MAX_PERCENT);
+}
+
+#[tokio::test]
+async fn bond_status_follows_auto_renew_and_close() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+    let asset_id = get_asset_id(&leaf.merkle_tree, leaf.nonce);
+
+    process(
+        context,
+        &[instructions::enable_auto_renew(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            &asset_id,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // the stored unbond timestamp is behind, auto renewing bonds stay locked
+    warp(context, 10, LOCK_PERIOD as i64 + 1).await;
+
+    let status = bond_status(context, &user.pubkey(), 1, &asset_id).await;
+    let now = clock(context).await.unix_timestamp as u64;
+    assert_eq!(status.owner, user.pubkey());
+    assert_eq!(status.state, State::Active.to_code());
+    assert_eq!(status.bond_amount, BOND_AMOUNT);
+    assert_eq!(status.unbond_timestamp, now + LOCK_PERIOD);
+    assert_eq!(status.liveliness, MAX_PERCENT);
+
+    process(
+        context,
+        &[instructions::disable_auto_renew(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            &asset_id,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    warp(context, 10, LOCK_PERIOD as i64 + 1).await;
+
+    process(
+        context,
+        &[
+            instructions::withdraw(&user.pubkey(), &setup.mint, BOND_CONFIG_INDEX, 1, None),
+            instructions::close_bond(&user.pubkey(), 1, &asset_id),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let status = bond_status(context, &user.pubkey(), 1, &asset_id).await;
+    assert_eq!(status.owner, Pubkey::default());
+    assert_eq!(status.state, State::Inactive.to_code());
+    assert_eq!(status.bond_amount, 0);
+    assert_eq!(status.unbond_timestamp, 0);
+
+    // the asset usage is closed with the bond, the asset can be bonded again
+    process(
+        context,
+        &[instructions::bond(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            2,
+            BOND_AMOUNT,
+            &leaf,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let status = bond_status(context, &user.pubkey(), 2, &asset_id).await;
+    assert_eq!(status.owner, user.pubkey());
+    assert_eq!(status.state, State::Active.to_code());
+}
+
+#[tokio::test]
+async fn withdraw_during_lock_period_is_penalized() {
+    let mut setup = setup().await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    warp(context, 10, (LOCK_PERIOD / 4) as i64).await;
+
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let penalty = BOND_AMOUNT * WITHDRAW_PENALTY / MAX_PERCENT;
+
+    assert_eq!(
+        token_balance(context, &user.pubkey(), &setup.mint).await,
+        USER_BALANCE - penalty
+    );
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.state, State::Inactive.to_code());
+    assert_eq!(bond.bond_amount, 0);
+
+    let vault_config = vault_config(context).await;
+    assert_eq!(vault_config.total_bond_amount, 0);
+    assert_eq!(vault_config.total_penalized_amount, penalty);
+}
+
+#[tokio::test]
+async fn cancel_unbond_keeps_the_full_bond() {
+    let mut setup = setup().await;
+    update_bond_config(&mut setup.context, |bond_config| {
+        bond_config.cooldown_period = 100
+    })
+    .await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    let penalty = BOND_AMOUNT * WITHDRAW_PENALTY / MAX_PERCENT;
+
+    process(
+        context,
+        &[instructions::start_unbond(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // the penalty is recorded, not charged
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.bond_amount, BOND_AMOUNT);
+    assert_eq!(bond.unbond_penalty, penalty);
+    assert_eq!(vault_config(context).await.total_penalized_amount, 0);
+
+    process(
+        context,
+        &[instructions::cancel_unbond(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.state, State::Active.to_code());
+    assert_eq!(bond.bond_amount, BOND_AMOUNT);
+    assert_eq!(bond.unbond_penalty, 0);
+
+    let vault_config_after_cancel = vault_config(context).await;
+    assert_eq!(vault_config_after_cancel.total_bond_amount, BOND_AMOUNT);
+    assert_eq!(vault_config_after_cancel.total_penalized_amount, 0);
+
+    // unbonding again inside the lock is charged once the cooldown is over
+    warp(context, 10, 10).await;
+    process(
+        context,
+        &[instructions::start_unbond(
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    warp(context, 10, 100).await;
+    process(
+        context,
+        &[instructions::complete_withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_balance(context, &user.pubkey(), &setup.mint).await,
+        USER_BALANCE - penalty
+    );
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.state, State::Inactive.to_code());
+    assert_eq!(bond.bond_amount, 0);
+    assert_eq!(bond.unbond_penalty, 0);
+
+    let vault_config = vault_config(context).await;
+    assert_eq!(vault_config.total_bond_amount, 0);
+    assert_eq!(vault_config.total_penalized_amount, penalty);
+}
+
+#[tokio::test]
+async fn withdraw_from_a_tier_with_bonding_paused() {
+    let mut setup = setup().await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    // the tier takes no new bonds, its open bonds can still leave
+    update_bond_config(context, |bond_config| {
+        bond_config.bond_state = State::Inactive.to_code();
+        bond_config.pause_flags = PauseFlag::Bond.to_code();
+    })
+    .await;
+
+    warp(context, 10, LOCK_PERIOD as i64 + 1).await;
+
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_balance(context, &user.pubkey(), &setup.mint).await,
+        USER_BALANCE
+    );
+    assert_eq!(
+        bond_account(context, &user.pubkey(), 1).await.state,
+        State::Inactive.to_code()
+    );
+}
+
+#[tokio::test]
+async fn top_up_and_withdraw_after_lock_period() {
+    let mut setup = setup().await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    warp(context, 10, (LOCK_PERIOD / 2) as i64).await;
+
+    let top_up_amount = BOND_AMOUNT / 2;
+    process(
+        context,
+        &[instructions::top_up(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            1,
+            top_up_amount,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    // a top up starts a new lock period
+    let timestamp = clock(context).await.unix_timestamp as u64;
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.bond_amount, BOND_AMOUNT + top_up_amount);
+    assert_eq!(bond.unbond_timestamp, timestamp + LOCK_PERIOD);
+    assert_eq!(liveliness(context, &user.pubkey()).await, MAX_PERCENT);
+
+    warp(context, 10, LOCK_PERIOD as i64 + 1).await;
+
+    process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let address_bonds_rewards = address_bonds_rewards(context, &user.pubkey()).await;
+    assert_eq!(address_bonds_rewards.address_total_bond_amount, 0);
+
+    let vault_config = vault_config(context).await;
+    assert_eq!(vault_config.total_bond_amount, 0);
+    assert_eq!(vault_config.total_penalized_amount, 0);
+
+    // rewards accrued while bonded stay claimable, the bond amount comes back in full
+    let balance = token_balance(context, &user.pubkey(), &setup.mint).await;
+    assert_eq!(balance, USER_BALANCE);
+}
+
+#[tokio::test]
+async fn claim_and_stake_rewards_match_quote() {
+    let mut setup = setup().await;
+    let leaf = bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    // a liveliness above the threshold pays out the full claimable amount
+    warp(context, 100, 10).await;
+    let quote = quote_payout(context, &user.pubkey()).await;
+    assert!(quote > 0);
+
+    process(
+        context,
+        &[instructions::claim_rewards(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            1,
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_balance(context, &user.pubkey(), &setup.mint).await,
+        USER_BALANCE - BOND_AMOUNT + quote
+    );
+    assert_eq!(
+        address_bonds_rewards(context, &user.pubkey())
+            .await
+            .claimable_amount,
+        0
+    );
+
+    // half way through the lock period the linear curve pays out the liveliness share
+    warp(context, 100, (LOCK_PERIOD / 2) as i64).await;
+    let quote = quote_payout(context, &user.pubkey()).await;
+    let claimable = claimable(context, &user.pubkey()).await;
+    let address_liveliness = liveliness(context, &user.pubkey()).await;
+    assert!(address_liveliness < 50_00);
+    assert_eq!(quote, claimable * address_liveliness / MAX_PERCENT);
+
+    process(
+        context,
+        &[instructions::stake_rewards(
+            &user.pubkey(),
+            &user.pubkey(),
+            BOND_CONFIG_INDEX,
+            1,
+            1,
+            &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+        )],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let bond = bond_account(context, &user.pubkey(), 1).await;
+    assert_eq!(bond.bond_amount, BOND_AMOUNT + quote);
+    assert_eq!(
+        vault_config(context).await.total_bond_amount,
+        BOND_AMOUNT + quote
+    );
+    assert_eq!(liveliness(context, &user.pubkey()).await, MAX_PERCENT);
+}
+
+#[tokio::test]
+async fn rewards_over_an_empty_vault_stay_in_reserve() {
+    let mut setup = setup().await;
+    let user = setup.user.insecure_clone();
+
+    // rewards are active before the first bond
+    warp(&mut setup.context, 100, 0).await;
+    let leaf = bond(&mut setup).await;
+    let context = &mut setup.context;
+
+    let rewards_config_at_bond = rewards_config(context).await;
+    assert_eq!(rewards_config_at_bond.rewards_reserve, REWARDS_RESERVE);
+    assert_eq!(rewards_config_at_bond.accumulated_rewards, 0);
+
+    // the only bonder claims and leaves in the same slot
+    warp(context, 100, 10).await;
+    process(
+        context,
+        &[
+            instructions::claim_rewards(&user.pubkey(), &setup.mint, BOND_CONFIG_INDEX, 1, 1),
+            instructions::withdraw(&user.pubkey(), &setup.mint, BOND_CONFIG_INDEX, 1, None),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let rewards_config_at_withdraw = rewards_config(context).await;
+    let distributed = (rewards_config_at_withdraw.last_reward_slot
+        - rewards_config_at_bond.last_reward_slot)
+        * REWARDS_PER_SLOT;
+    assert!(distributed > 0);
+    assert_eq!(rewards_config_at_withdraw.accumulated_rewards, distributed);
+    assert_eq!(vault_config(context).await.total_bond_amount, 0);
+
+    // accruing over the empty vault moves the reward slot without touching the reserve
+    warp(context, 100, 10).await;
+    process(
+        context,
+        &[
+            instructions::close_bond(
+                &user.pubkey(),
+                1,
+                &get_asset_id(&leaf.merkle_tree, leaf.nonce),
+            ),
+            instructions::close_address(&user.pubkey()),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    let rewards_config = rewards_config(context).await;
+    assert!(rewards_config.last_reward_slot > rewards_config_at_withdraw.last_reward_slot);
+    assert_eq!(rewards_config.accumulated_rewards, distributed);
+    assert_eq!(
+        rewards_config.rewards_reserve,
+        REWARDS_RESERVE - distributed
+    );
+}
+
+#[tokio::test]
+async fn bond_past_total_bond_amount_overflows() {
+    let mut setup = setup().await;
+    set_total_bond_amount(&mut setup.context, u64::MAX - BOND_AMOUNT + 1).await;
+
+    let leaf = mint_cnft(&mut setup).await;
+    let user = setup.user.insecure_clone();
+
+    let result = process(
+        &mut setup.context,
+        &[instructions::bond(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            BOND_AMOUNT,
+            &leaf,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::MathOverflow);
+}
+
+#[tokio::test]
+async fn withdraw_below_total_bond_amount_underflows() {
+    let mut setup = setup().await;
+    bond(&mut setup).await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    set_total_bond_amount(context, BOND_AMOUNT - 1).await;
+
+    let result = process(
+        context,
+        &[instructions::withdraw(
+            &user.pubkey(),
+            &setup.mint,
+            BOND_CONFIG_INDEX,
+            1,
+            None,
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::MathUnderflow);
+}
+
+#[tokio::test]
+async fn migrations_widen_rewards_per_share() {
+    let mut setup = setup().await;
+    let user = setup.user.insecure_clone();
+    let context = &mut setup.context;
+
+    let (rewards_config_address, _) = pda::find_rewards_config();
+    let (address_bonds_rewards_address, _) = pda::find_address_bonds_rewards(&user.pubkey());
+
+    let mut expected_rewards_config = rewards_config(context).await;
+    let mut expected_address_bonds_rewards = address_bonds_rewards(context, &user.pubkey()).await;
+
+    context.set_account(
+        &rewards_config_address,
+        &legacy_account(
+            &expected_rewards_config,
+            RewardsConfig::REWARDS_PER_SHARE_OFFSET,
+            5 * DIVISION_SAFETY_CONST,
+        )
+        .into(),
+    );
+    context.set_account(
+        &address_bonds_rewards_address,
+        &legacy_account(
+            &expected_address_bonds_rewards,
+            AddressBondsRewards::REWARDS_PER_SHARE_OFFSET,
+            3 * DIVISION_SAFETY_CONST,
+        )
+        .into(),
+    );
+
+    process(
+        context,
+        &[
+            instructions::migrate_rewards_config(&user.pubkey()),
+            instructions::migrate_address(&user.pubkey(), &user.pubkey()),
+        ],
+        &[&user],
+    )
+    .await
+    .unwrap();
+
+    expected_rewards_config.rewards_per_share = 5 * REWARDS_PER_SHARE_PRECISION;
+    expected_address_bonds_rewards.address_rewards_per_share = 3 * REWARDS_PER_SHARE_PRECISION;
+
+    assert_eq!(
+        account_data(context, &rewards_config_address).await,
+        anchor_account(&expected_rewards_config).data
+    );
+    assert_eq!(
+        account_data(context, &address_bonds_rewards_address).await,
+        anchor_account(&expected_address_bonds_rewards).data
+    );
+
+    let result = process(
+        context,
+        &[instructions::migrate_rewards_config(&user.pubkey())],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::AlreadyMigrated);
+
+    let result = process(
+        context,
+        &[instructions::migrate_address(
+            &user.pubkey(),
+            &user.pubkey(),
+        )],
+        &[&user],
+    )
+    .await;
+    assert_program_error(result, Errors::AlreadyMigrated);
+
+    // the migrated accounts deserialize in the bonding flow
+    bond(&mut setup).await;
+
+    let address_bonds_rewards = address_bonds_rewards(&mut setup.context, &user.pubkey()).await;
+    assert_eq!(
+        address_bonds_rewards.address_rewards_per_share,
+        rewards_config(&mut setup.context).await.rewards_per_share
+    );
+    assert_eq!(address_bonds_rewards.address_total_bond_amount, BOND_AMOUNT);
+}
+
+// quote against the slot and timestamp the next transaction executes in
+async fn quote_payout(context: &mut ProgramTestContext, owner: &Pubkey) -> u64 {
+    let clock = clock(context).await;
+
+    math::quote_rewards_payout(
+        &rewards_config(context).await,
+        &vault_config(context).await,
+        &address_bonds_rewards(context, owner).await,
+        clock.slot,
+        clock.unix_timestamp as u64,
+    )
+    .unwrap()
+}
+
+async fn claimable(context: &mut ProgramTestContext, owner: &Pubkey) -> u64 {
+    let clock = clock(context).await;
+
+    math::compute_claimable_rewards(
+        &rewards_config(context).await,
+        &vault_config(context).await,
+        &address_bonds_rewards(context, owner).await,
+        clock.slot,
+    )
+    .unwrap()
+}
@@ -6,9 +6,18 @@ pub const ADDRESS_BONDS_REWARDS_SEED: &str = "address_bonds_rewards";
 pub const BOND_CONFIG_SEED: &str = "bond_config";
 pub const REWARDS_CONFIG_SEED: &str = "rewards_config";
 pub const VAULT_CONFIG_SEED: &str = "vault_config";
+pub const ASSET_AUTHORITY_SEED: &str = "asset_authority";
+pub const PROGRAM_CONFIG_SEED: &str = "program_config";
+pub const CHALLENGE_SEED: &str = "challenge";
 
 pub const MAX_PERCENT: u64 = 10_000;
 pub const SLOTS_IN_YEAR: u64 = 78_840_000u64;
 pub const DIVISION_SAFETY_CONST: u64 = 1_000_000_000;
+pub const REWARDS_PER_SHARE_PRECISION: u128 = 1_000_000_000_000_000_000;
+pub const PAUSE_FLAGS_MASK: u8 = 0b0011_1111;
+pub const DEFAULT_LIVELINESS_THRESHOLD: u64 = 95_00;
+pub const DEFAULT_CHALLENGE_PERIOD: u64 = 604_800;
+
+pub const MAX_BONDS_PER_BATCH: usize = 10;
 
 pub const ADMIN_PUBKEY: Pubkey = pubkey!("1KsJeTvmJaWsAdZba7V7sxQ7zPFKQp1seh2XP9ZHnsd");
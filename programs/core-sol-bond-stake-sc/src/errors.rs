@@ -42,4 +42,50 @@ pub enum Errors {
     AssetIdMismatch,
     #[msg("Vault bond id mismatch")]
     VaultBondIdMismatch,
+    #[msg("Leaf delegate mismatch")]
+    LeafDelegateMismatch,
+    #[msg("Missing accounts")]
+    MissingAccounts,
+    #[msg("Bond is slashed")]
+    BondIsSlashed,
+    #[msg("Bond is challenged")]
+    BondIsChallenged,
+    #[msg("Challenges are disabled")]
+    ChallengesDisabled,
+    #[msg("Asset mode not supported")]
+    AssetModeNotSupported,
+    #[msg("Bond is locked")]
+    BondIsLocked,
+    #[msg("Unbonding required")]
+    UnbondingRequired,
+    #[msg("Bond is not unbonding")]
+    BondIsNotUnbonding,
+    #[msg("Cooldown not over")]
+    CooldownNotOver,
+    #[msg("Bond is active")]
+    BondIsActive,
+    #[msg("Vault bond cannot be closed")]
+    VaultBondCannotBeClosed,
+    #[msg("Address has open bonds")]
+    AddressHasOpenBonds,
+    #[msg("Unclaimed rewards")]
+    UnclaimedRewards,
+    #[msg("Auto renew is disabled")]
+    AutoRenewDisabled,
+    #[msg("Account already migrated")]
+    AlreadyMigrated,
+    #[msg("Emergency state is inactive")]
+    EmergencyStateInactive,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Math underflow")]
+    MathUnderflow,
+    #[msg("Challenge period not over")]
+    ChallengeNotExpired,
+    #[msg("Bond amount too low")]
+    BondAmountTooLow,
+    #[msg("Address has unsynced bonds")]
+    AddressNotSynced,
+    #[msg("Bond config mismatch")]
+    BondConfigMismatch,
 }
@@ -2,7 +2,7 @@ use std::ops::DerefMut;
 
 use anchor_lang::prelude::*;
 
-use crate::{BondConfig, State, ADMIN_PUBKEY, BOND_CONFIG_SEED};
+use crate::{AssetMode, BondConfig, State, ADMIN_PUBKEY, BOND_CONFIG_SEED};
 
 #[derive(Accounts)]
 #[instruction(index: u8)]
@@ -44,7 +44,10 @@ pub fn create_bond_config(
     bond_config.lock_period = lock_period;
     bond_config.bond_amount = bond_amount;
     bond_config.withdraw_penalty = withdraw_penalty;
-    bond_config.padding = [0; 32];
+    bond_config.asset_mode = AssetMode::Unrestricted.to_code();
+    bond_config.cooldown_period = 0;
+    bond_config.pause_flags = 0;
+    bond_config.padding = [0; 22];
 
     Ok(())
 }
@@ -57,7 +57,8 @@ pub fn initialize_vault(
     vault_config.mint_of_token = ctx.accounts.mint_of_token.key();
     vault_config.total_bond_amount = 0;
     vault_config.total_penalized_amount = 0;
-    vault_config.padding = [0; 32];
+    vault_config.total_challenge_amount = 0;
+    vault_config.padding = [0; 24];
 
    
 
@@ -4,7 +4,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token::Token};
 
 use crate::{
-    BondConfig, RewardsConfig, State, ADMIN_PUBKEY, BOND_CONFIG_SEED, REWARDS_CONFIG_SEED,
+    AssetMode, BondConfig, LivelinessCurve, RewardsConfig, State, ADMIN_PUBKEY, BOND_CONFIG_SEED,
+    DEFAULT_LIVELINESS_THRESHOLD, REWARDS_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -60,7 +61,10 @@ pub fn initialize_contract(
     bond_config.lock_period = lock_period;
     bond_config.bond_amount = bond_amount;
     bond_config.withdraw_penalty = withdraw_penalty;
-    bond_config.padding = [0; 32];
+    bond_config.asset_mode = AssetMode::Unrestricted.to_code();
+    bond_config.cooldown_period = 0;
+    bond_config.pause_flags = 0;
+    bond_config.padding = [0; 22];
 
     let rewards_config = ctx.accounts.rewards_config.deref_mut();
 
@@ -72,7 +76,10 @@ pub fn initialize_contract(
     rewards_config.rewards_per_share = 0;
     rewards_config.last_reward_slot = 0;
     rewards_config.max_apr = max_apr;
-    rewards_config.padding = [0; 32];
+    rewards_config.liveliness_curve = LivelinessCurve::Linear.to_code();
+    rewards_config.liveliness_threshold = DEFAULT_LIVELINESS_THRESHOLD;
+    rewards_config.liveliness_curve_param = 0;
+    rewards_config.padding = [0; 15];
 
     Ok(())
 }
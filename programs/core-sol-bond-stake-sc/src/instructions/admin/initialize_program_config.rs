@@ -0,0 +1,51 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    Errors, ProgramConfig, SlashDestination, State, ADMIN_PUBKEY, DEFAULT_CHALLENGE_PERIOD,
+    MAX_PERCENT, PROGRAM_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(
+        init,
+        payer=authority,
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump,
+        space=ProgramConfig::INIT_SPACE
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        address=ADMIN_PUBKEY,
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_program_config(
+    ctx: Context<InitializeProgramConfig>,
+    slashing_authority: Pubkey,
+    slash_share: u64,
+) -> Result<()> {
+    require!(slash_share <= MAX_PERCENT, Errors::WrongValue);
+
+    let program_config = ctx.accounts.program_config.deref_mut();
+
+    program_config.bump = ctx.bumps.program_config;
+    program_config.slashing_authority = slashing_authority;
+    program_config.slash_share = slash_share;
+    program_config.slash_destination = SlashDestination::Treasury.to_code();
+    program_config.arbiter = slashing_authority;
+    program_config.challenge_amount = 0;
+    program_config.emergency_state = State::Inactive.to_code();
+    program_config.pause_flags = 0;
+    program_config.challenge_period = DEFAULT_CHALLENGE_PERIOD;
+    program_config.padding = [0; 14];
+
+    Ok(())
+}
@@ -10,3 +10,7 @@ pub mod rewards;
 pub use rewards::*;
 pub mod initalize_vault;
 pub use initalize_vault::*;
+pub mod initialize_program_config;
+pub use initialize_program_config::*;
+pub mod update_program_config;
+pub use update_program_config::*;
@@ -59,7 +59,10 @@ pub struct RewardsContext<'info> {
 
 pub fn add_rewards(ctx: Context<RewardsContext>, amount: u64) -> Result<()> {
     let rewards_config = &mut ctx.accounts.rewards_config;
-    rewards_config.rewards_reserve += amount;
+    rewards_config.rewards_reserve = rewards_config
+        .rewards_reserve
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
 
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.authority_token_account.to_account_info(),
@@ -81,12 +84,20 @@ pub fn remove_rewards(ctx: Context<RewardsContext>, amount: u64) -> Result<()> {
         &[ctx.accounts.vault_config.bump],
     ]];
     let rewards_config = &mut ctx.accounts.rewards_config;
-    rewards_config.rewards_reserve -= amount;
-
-    require!(
-        ctx.accounts.vault.amount >= amount,
-        Errors::NotEnoughBalance
-    );
+    rewards_config.rewards_reserve = rewards_config
+        .rewards_reserve
+        .checked_sub(amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    // open counter-bonds belong to the challengers
+    let available_amount = ctx
+        .accounts
+        .vault
+        .amount
+        .checked_sub(ctx.accounts.vault_config.total_challenge_amount)
+        .ok_or(Errors::NotEnoughBalance)?;
+
+    require!(available_amount >= amount, Errors::NotEnoughBalance);
 
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault.to_account_info(),
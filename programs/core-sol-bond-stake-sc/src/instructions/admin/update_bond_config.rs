@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::{BondConfig, Errors, ADMIN_PUBKEY, BOND_CONFIG_SEED, MAX_PERCENT};
+use crate::{
+    AssetMode, BondConfig, Errors, ADMIN_PUBKEY, BOND_CONFIG_SEED, MAX_PERCENT, PAUSE_FLAGS_MASK,
+};
 
 #[derive(Accounts)]
 #[instruction(index:u8)]
@@ -52,3 +54,26 @@ pub fn update_withdraw_penalty(
     bond_config.withdraw_penalty = withdraw_penalty;
     Ok(())
 }
+
+pub fn update_cooldown_period(ctx: Context<UpdateBondConfig>, cooldown_period: u64) -> Result<()> {
+    let bond_config = &mut ctx.accounts.bond_config;
+    bond_config.cooldown_period = cooldown_period;
+    Ok(())
+}
+
+pub fn update_pause_flags(ctx: Context<UpdateBondConfig>, pause_flags: u8) -> Result<()> {
+    require!(pause_flags & !PAUSE_FLAGS_MASK == 0, Errors::WrongValue);
+    let bond_config = &mut ctx.accounts.bond_config;
+    bond_config.pause_flags = pause_flags;
+    Ok(())
+}
+
+pub fn update_asset_mode(ctx: Context<UpdateBondConfig>, asset_mode: u8) -> Result<()> {
+    require!(
+        asset_mode <= AssetMode::Escrow.to_code(),
+        Errors::WrongValue
+    );
+    let bond_config = &mut ctx.accounts.bond_config;
+    bond_config.asset_mode = asset_mode;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Errors, ProgramConfig, SlashDestination, ADMIN_PUBKEY, MAX_PERCENT, PAUSE_FLAGS_MASK,
+    PROGRAM_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    #[account(
+        mut,
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        address=ADMIN_PUBKEY
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn update_slashing_authority(
+    ctx: Context<UpdateProgramConfig>,
+    slashing_authority: Pubkey,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.slashing_authority = slashing_authority;
+    Ok(())
+}
+
+pub fn update_slash_share(ctx: Context<UpdateProgramConfig>, slash_share: u64) -> Result<()> {
+    require!(slash_share <= MAX_PERCENT, Errors::WrongValue);
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.slash_share = slash_share;
+    Ok(())
+}
+
+pub fn update_slash_destination(
+    ctx: Context<UpdateProgramConfig>,
+    slash_destination: u8,
+) -> Result<()> {
+    require!(
+        slash_destination <= SlashDestination::RewardsReserve.to_code(),
+        Errors::WrongValue
+    );
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.slash_destination = slash_destination;
+    Ok(())
+}
+
+pub fn update_arbiter(ctx: Context<UpdateProgramConfig>, arbiter: Pubkey) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.arbiter = arbiter;
+    Ok(())
+}
+
+pub fn update_challenge_amount(
+    ctx: Context<UpdateProgramConfig>,
+    challenge_amount: u64,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.challenge_amount = challenge_amount;
+    Ok(())
+}
+
+pub fn update_challenge_period(
+    ctx: Context<UpdateProgramConfig>,
+    challenge_period: u64,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.challenge_period = challenge_period;
+    Ok(())
+}
+
+pub fn update_emergency_state(ctx: Context<UpdateProgramConfig>, state: u8) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.emergency_state = state;
+    Ok(())
+}
+
+pub fn update_global_pause_flags(ctx: Context<UpdateProgramConfig>, pause_flags: u8) -> Result<()> {
+    require!(pause_flags & !PAUSE_FLAGS_MASK == 0, Errors::WrongValue);
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.pause_flags = pause_flags;
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{get_current_slot, RewardsConfig, State, ADMIN_PUBKEY, REWARDS_CONFIG_SEED};
+use crate::{
+    get_current_slot, Errors, LivelinessCurve, RewardsConfig, State, ADMIN_PUBKEY, MAX_PERCENT,
+    REWARDS_CONFIG_SEED,
+};
 
 #[derive(Accounts)]
 pub struct UpdateRewardsConfig<'info> {
@@ -41,3 +44,25 @@ pub fn update_max_apr(ctx: Context<UpdateRewardsConfig>, max_apr: u64) -> Result
     rewards_config.max_apr = max_apr;
     Ok(())
 }
+
+pub fn update_liveliness_curve(
+    ctx: Context<UpdateRewardsConfig>,
+    liveliness_curve: u8,
+    liveliness_threshold: u64,
+    liveliness_curve_param: u64,
+) -> Result<()> {
+    require!(
+        liveliness_curve <= LivelinessCurve::QuadraticDecay.to_code(),
+        Errors::WrongValue
+    );
+    require!(
+        liveliness_threshold > 0 && liveliness_threshold <= MAX_PERCENT,
+        Errors::WrongValue
+    );
+    require!(liveliness_curve_param <= MAX_PERCENT, Errors::WrongValue);
+    let rewards_config = &mut ctx.accounts.rewards_config;
+    rewards_config.liveliness_curve = liveliness_curve;
+    rewards_config.liveliness_threshold = liveliness_threshold;
+    rewards_config.liveliness_curve_param = liveliness_curve_param;
+    Ok(())
+}
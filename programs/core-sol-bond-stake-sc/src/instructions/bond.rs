@@ -1,17 +1,17 @@
-use std::ops::Add;
-
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, TransferChecked},
 };
 use mpl_bubblegum::{types::LeafSchema, utils::get_asset_id};
-use spl_account_compression::program::SplAccountCompression;
+use spl_account_compression::{program::SplAccountCompression, Noop};
 
 use crate::{
-    get_current_timestamp, update_address_claimable_rewards, AddressBondsRewards, AssetUsage, Bond,
-    BondConfig, Errors, RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED,
-    BOND_CONFIG_SEED, BOND_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+    add_bond_liveliness, delegate_asset, get_current_timestamp, transfer_asset,
+    update_address_claimable_rewards, AddressBondsRewards, AssetMode, AssetUsage, Bond, BondConfig,
+    BubblegumAccounts, Errors, LeafArgs, ProgramConfig, RewardsConfig, State, VaultConfig,
+    ADDRESS_BONDS_REWARDS_SEED, ASSET_AUTHORITY_SEED, BOND_CONFIG_SEED, BOND_SEED,
+    PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -36,7 +36,7 @@ pub struct BondContext<'info> {
     #[account(
         init,
         payer = authority,
-        constraint=address_bonds_rewards.current_index + 1 == bond_id  @ Errors::WrongBondId,
+        constraint=address_bonds_rewards.current_index.checked_add(1) == Some(bond_id)  @ Errors::WrongBondId,
         seeds = [
             BOND_SEED.as_bytes(),
             authority.key().as_ref(),
@@ -53,6 +53,12 @@ pub struct BondContext<'info> {
     )]
     pub bond_config: Box<Account<'info, BondConfig>>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     #[account(
         mut,
         seeds=[REWARDS_CONFIG_SEED.as_bytes()],
@@ -88,10 +94,21 @@ pub struct BondContext<'info> {
 
     /// CHECK: unsafe
     #[account(
+        mut,
         constraint= merkle_tree.key() == bond_config.merkle_tree.key() @ Errors::MerkleTreeMismatch,
     )]
     pub merkle_tree: UncheckedAccount<'info>,
 
+    /// CHECK: only used to rebuild the leaf
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: pda
+    #[account(
+        seeds=[ASSET_AUTHORITY_SEED.as_bytes()],
+        bump,
+    )]
+    pub asset_authority: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint=authority_token_account.amount >= amount @ Errors::NotEnoughBalance,
@@ -105,6 +122,15 @@ pub struct BondContext<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub compression_program: Program<'info, SplAccountCompression>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: bubblegum program
+    #[account(address=mpl_bubblegum::ID)]
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+
+    pub log_wrapper: Option<Program<'info, Noop>>,
 }
 
 pub fn bond<'a, 'b, 'c: 'info, 'info>(
@@ -132,7 +158,19 @@ pub fn bond<'a, 'b, 'c: 'info, 'info>(
     let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
 
     address_bonds_rewards.last_update_timestamp = current_timestamp;
-    address_bonds_rewards.address_total_bond_amount += amount;
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    let asset_mode = ctx.accounts.bond_config.asset_mode;
+
+    if asset_mode == AssetMode::DelegateRequired.to_code() {
+        require!(
+            ctx.accounts.leaf_delegate.key() == ctx.accounts.asset_authority.key(),
+            Errors::LeafDelegateMismatch
+        );
+    }
 
     // check leaf owner here
     let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), nonce);
@@ -140,7 +178,7 @@ pub fn bond<'a, 'b, 'c: 'info, 'info>(
     let leaf = LeafSchema::V1 {
         id: asset_id,
         owner: ctx.accounts.authority.key(),
-        delegate: ctx.accounts.authority.key(),
+        delegate: ctx.accounts.leaf_delegate.key(),
         nonce,
         data_hash,
         creator_hash,
@@ -155,13 +193,66 @@ pub fn bond<'a, 'b, 'c: 'info, 'info>(
 
     spl_account_compression::cpi::verify_leaf(cpi_ctx, root, leaf.hash(), nonce as u32)?;
 
+    // hand the leaf over to the program for the bond duration
+    if asset_mode == AssetMode::Delegate.to_code() || asset_mode == AssetMode::Escrow.to_code() {
+        let (Some(tree_config), Some(bubblegum_program), Some(log_wrapper)) = (
+            &ctx.accounts.tree_config,
+            &ctx.accounts.bubblegum_program,
+            &ctx.accounts.log_wrapper,
+        ) else {
+            return err!(Errors::MissingAccounts);
+        };
+
+        let bubblegum_accounts = BubblegumAccounts {
+            bubblegum_program,
+            tree_config,
+            merkle_tree: &ctx.accounts.merkle_tree,
+            log_wrapper,
+            compression_program: &ctx.accounts.compression_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        let leaf_args = LeafArgs {
+            nonce,
+            root,
+            data_hash,
+            creator_hash,
+        };
+
+        if asset_mode == AssetMode::Delegate.to_code() {
+            delegate_asset(
+                &bubblegum_accounts,
+                &ctx.accounts.authority,
+                &ctx.accounts.leaf_delegate,
+                &ctx.accounts.asset_authority,
+                ctx.remaining_accounts,
+                &leaf_args,
+            )?;
+        } else {
+            transfer_asset(
+                &bubblegum_accounts,
+                &ctx.accounts.authority,
+                &ctx.accounts.leaf_delegate,
+                &ctx.accounts.asset_authority,
+                ctx.remaining_accounts,
+                &leaf_args,
+                &[],
+            )?;
+        }
+    }
+
     let current_timestamp = get_current_timestamp()?;
 
-    ctx.accounts
+    let total_bond_amount = ctx
+        .accounts
         .vault_config
         .total_bond_amount
         .checked_add(amount)
-        .unwrap();
+        .ok_or(Errors::MathOverflow)?;
+
+    let unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
 
     // bond transfer to vault
 
@@ -180,18 +271,48 @@ pub fn bond<'a, 'b, 'c: 'info, 'info>(
         ctx.accounts.mint_of_token_sent.decimals,
     )?;
 
+    // addresses without unsynced bonds stay synced
+    if address_bonds_rewards.synced_index == address_bonds_rewards.current_index {
+        address_bonds_rewards.synced_index = bond_id;
+    }
     address_bonds_rewards.current_index = bond_id;
-    ctx.accounts.vault_config.total_bond_amount += amount;
+    address_bonds_rewards.open_bonds = address_bonds_rewards
+        .open_bonds
+        .checked_add(1)
+        .ok_or(Errors::MathOverflow)?;
+    ctx.accounts.vault_config.total_bond_amount = total_bond_amount;
 
     ctx.accounts.bond.set_inner(Bond {
         bump: ctx.bumps.bond,
         state: State::Active.to_code(),
-        unbond_timestamp: current_timestamp.add(ctx.accounts.bond_config.lock_period),
+        unbond_timestamp,
         bond_timestamp: current_timestamp,
         bond_amount: amount,
         asset_id: asset_id.key(),
         owner: ctx.accounts.authority.key(),
-        padding: [0; 64],
+        asset_mode,
+        slash_reason: 0,
+        challenged: 0,
+        cooldown_end_timestamp: 0,
+        auto_renew: 0,
+        counted: 1,
+        lock_expired: 0,
+        unbond_penalty: 0,
+        padding: [0; 42],
+    });
+
+    add_bond_liveliness(
+        &mut ctx.accounts.address_bonds_rewards,
+        &mut ctx.accounts.bond,
+        current_timestamp,
+    )?;
+
+    ctx.accounts.asset_usage.set_inner(AssetUsage {
+        bond: ctx.accounts.bond.key(),
+        owner: ctx.accounts.authority.key(),
+        bond_id,
+        bond_config_index: ctx.accounts.bond_config.index,
+        padding: [0; 32],
     });
 
     Ok(())
@@ -0,0 +1,422 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+use mpl_bubblegum::{types::LeafSchema, utils::get_asset_id};
+use spl_account_compression::program::SplAccountCompression;
+
+use crate::{
+    add_bond_liveliness, get_current_timestamp, update_address_claimable_rewards,
+    AddressBondsRewards, AssetMode, AssetUsage, Bond, BondConfig, Errors, ProgramConfig,
+    RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, ASSET_AUTHORITY_SEED,
+    BOND_CONFIG_SEED, BOND_SEED, MAX_BONDS_PER_BATCH, PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED,
+    VAULT_CONFIG_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BondManyArgs {
+    pub nonce: u64,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub leaf_delegate: Pubkey,
+    pub proof_len: u8,
+    pub bond_bump: u8,
+    pub asset_usage_bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(bond_config_index: u8)]
+pub struct BondMany<'info> {
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        seeds=[BOND_CONFIG_SEED.as_bytes(),&bond_config_index.to_be_bytes()],
+        bump=bond_config.bump,
+    )]
+    pub bond_config: Box<Account<'info, BondConfig>>,
+
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+    )]
+    pub rewards_config: Box<Account<'info, RewardsConfig>>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+        has_one=vault,
+    )]
+    pub vault_config: Box<Account<'info, VaultConfig>>,
+
+    #[account(
+        mut,
+        associated_token::mint=mint_of_token_sent,
+        associated_token::authority=vault_config
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint=mint_of_token_sent.key()==vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub mint_of_token_sent: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint=address_bonds_rewards.address == authority.key() @ Errors::OwnerMismatch,
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: unsafe
+    #[account(
+        constraint= merkle_tree.key() == bond_config.merkle_tree.key() @ Errors::MerkleTreeMismatch,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: pda
+    #[account(
+        seeds=[ASSET_AUTHORITY_SEED.as_bytes()],
+        bump,
+    )]
+    pub asset_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint=authority_token_account.owner==authority.key() @ Errors::OwnerMismatch,
+        constraint=authority_token_account.mint==vault_config.mint_of_token @ Errors::MintMismatch,
+    )
+    ]
+    pub authority_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+// remaining accounts hold, for every asset: bond, asset usage, proof
+pub fn bond_many<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, BondMany<'info>>,
+    root: [u8; 32],
+    assets: Vec<BondManyArgs>,
+) -> Result<()> {
+    require!(
+        !assets.is_empty() && assets.len() <= MAX_BONDS_PER_BATCH,
+        Errors::WrongValue
+    );
+
+    let asset_mode = ctx.accounts.bond_config.asset_mode;
+
+    require!(
+        asset_mode == AssetMode::Unrestricted.to_code()
+            || asset_mode == AssetMode::DelegateRequired.to_code(),
+        Errors::AssetModeNotSupported
+    );
+
+    let bond_amount = ctx.accounts.bond_config.bond_amount;
+    let amount = bond_amount
+        .checked_mul(assets.len() as u64)
+        .ok_or(Errors::MathOverflow)?;
+
+    require!(
+        ctx.accounts.authority_token_account.amount >= amount,
+        Errors::NotEnoughBalance
+    );
+
+    let current_timestamp = get_current_timestamp()?;
+    let unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
+
+    update_address_claimable_rewards(
+        &mut ctx.accounts.rewards_config,
+        &ctx.accounts.vault_config,
+        &mut ctx.accounts.address_bonds_rewards,
+    )?;
+
+    let authority = ctx.accounts.authority.key();
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+
+    for asset in assets.iter() {
+        let proof_len = asset.proof_len as usize;
+
+        require!(
+            remaining_accounts.len() >= 2 + proof_len,
+            Errors::InvalidRemainingAccounts
+        );
+
+        let (asset_accounts, rest) = remaining_accounts.split_at(2 + proof_len);
+        remaining_accounts = rest;
+
+        let bond_info = &asset_accounts[0];
+        let asset_usage_info = &asset_accounts[1];
+        let proof = &asset_accounts[2..];
+
+        if asset_mode == AssetMode::DelegateRequired.to_code() {
+            require!(
+                asset.leaf_delegate == ctx.accounts.asset_authority.key(),
+                Errors::LeafDelegateMismatch
+            );
+        }
+
+        let bond_id = ctx
+            .accounts
+            .address_bonds_rewards
+            .current_index
+            .checked_add(1)
+            .ok_or(Errors::MathOverflow)?;
+        let asset_id = get_asset_id(&merkle_tree, asset.nonce);
+
+        let bond_bump = asset.bond_bump;
+        let asset_usage_bump = asset.asset_usage_bump;
+        let bond_key = Pubkey::create_program_address(
+            &[
+                BOND_SEED.as_bytes(),
+                authority.as_ref(),
+                &bond_id.to_le_bytes(),
+                &[bond_bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| Errors::InvalidRemainingAccounts)?;
+        let asset_usage_key = Pubkey::create_program_address(
+            &[asset_id.as_ref(), &[asset_usage_bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| Errors::InvalidRemainingAccounts)?;
+
+        // bond and challenge derive the asset usage with the canonical bump, any other bump
+        // would let the same asset be bonded twice
+        require!(
+            (asset_usage_bump..u8::MAX).all(|bump| {
+                Pubkey::create_program_address(&[asset_id.as_ref(), &[bump + 1]], ctx.program_id)
+                    .is_err()
+            }),
+            Errors::InvalidRemainingAccounts
+        );
+
+        // the stored bond bump is used by every later instruction on the bond
+        require!(
+            (bond_bump..u8::MAX).all(|bump| {
+                Pubkey::create_program_address(
+                    &[
+                        BOND_SEED.as_bytes(),
+                        authority.as_ref(),
+                        &bond_id.to_le_bytes(),
+                        &[bump + 1],
+                    ],
+                    ctx.program_id,
+                )
+                .is_err()
+            }),
+            Errors::InvalidRemainingAccounts
+        );
+
+        require!(
+            bond_info.key() == bond_key && asset_usage_info.key() == asset_usage_key,
+            Errors::InvalidRemainingAccounts
+        );
+
+        // check leaf owner here
+        let leaf = LeafSchema::V1 {
+            id: asset_id,
+            owner: authority,
+            delegate: asset.leaf_delegate,
+            nonce: asset.nonce,
+            data_hash: asset.data_hash,
+            creator_hash: asset.creator_hash,
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.compression_program.to_account_info(),
+            spl_account_compression::cpi::accounts::VerifyLeaf {
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(proof.to_vec());
+
+        spl_account_compression::cpi::verify_leaf(cpi_ctx, root, leaf.hash(), asset.nonce as u32)?;
+
+        create_pda_account(
+            &ctx.accounts.authority,
+            asset_usage_info,
+            &ctx.accounts.system_program,
+            AssetUsage::INIT_SPACE,
+            &[asset_id.as_ref(), &[asset_usage_bump]],
+            ctx.program_id,
+        )?;
+        AssetUsage {
+            bond: bond_key,
+            owner: authority,
+            bond_id,
+            bond_config_index: ctx.accounts.bond_config.index,
+            padding: [0; 32],
+        }
+        .try_serialize(&mut &mut asset_usage_info.try_borrow_mut_data()?[..])?;
+
+        create_pda_account(
+            &ctx.accounts.authority,
+            bond_info,
+            &ctx.accounts.system_program,
+            Bond::INIT_SPACE,
+            &[
+                BOND_SEED.as_bytes(),
+                authority.as_ref(),
+                &bond_id.to_le_bytes(),
+                &[bond_bump],
+            ],
+            ctx.program_id,
+        )?;
+        let mut bond = Bond {
+            bump: bond_bump,
+            state: State::Active.to_code(),
+            unbond_timestamp,
+            bond_timestamp: current_timestamp,
+            bond_amount,
+            asset_id,
+            owner: authority,
+            asset_mode,
+            slash_reason: 0,
+            challenged: 0,
+            cooldown_end_timestamp: 0,
+            auto_renew: 0,
+            counted: 1,
+            lock_expired: 0,
+            unbond_penalty: 0,
+            padding: [0; 42],
+        };
+        add_bond_liveliness(
+            &mut ctx.accounts.address_bonds_rewards,
+            &mut bond,
+            current_timestamp,
+        )?;
+
+        bond.try_serialize(&mut &mut bond_info.try_borrow_mut_data()?[..])?;
+
+        let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+        // addresses without unsynced bonds stay synced
+        if address_bonds_rewards.synced_index == address_bonds_rewards.current_index {
+            address_bonds_rewards.synced_index = bond_id;
+        }
+        address_bonds_rewards.current_index = bond_id;
+        address_bonds_rewards.open_bonds = address_bonds_rewards
+            .open_bonds
+            .checked_add(1)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    require!(
+        remaining_accounts.is_empty(),
+        Errors::InvalidRemainingAccounts
+    );
+
+    // bond transfer to vault
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint_of_token_sent.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    token::transfer_checked(
+        cpi_context,
+        amount,
+        ctx.accounts.mint_of_token_sent.decimals,
+    )?;
+
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+    address_bonds_rewards.last_update_timestamp = current_timestamp;
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    ctx.accounts.vault_config.total_bond_amount = ctx
+        .accounts
+        .vault_config
+        .total_bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(())
+}
+
+// mirrors anchor init, an account already holding lamports is topped up, allocated and assigned
+// instead of created so a transfer to the address cannot block the bond
+fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    seeds: &[&[u8]],
+    owner: &Pubkey,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if lamports == 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+
+        return create_account(cpi_ctx.with_signer(&[seeds]), rent, space as u64, owner);
+    }
+
+    if rent > lamports {
+        let cpi_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+
+        transfer(cpi_ctx, rent - lamports)?;
+    }
+
+    let cpi_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        Allocate {
+            account_to_allocate: account.clone(),
+        },
+    );
+
+    allocate(cpi_ctx.with_signer(&[seeds]), space as u64)?;
+
+    let cpi_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        Assign {
+            account_to_assign: account.clone(),
+        },
+    );
+
+    assign(cpi_ctx.with_signer(&[seeds]), owner)
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compute_address_liveliness, get_current_timestamp, get_unbond_timestamp, AddressBondsRewards,
+    AssetUsage, Bond, BondConfig, Errors, State, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BondStatus {
+    pub owner: Pubkey,
+    pub state: u8,
+    pub bond_amount: u64,
+    pub unbond_timestamp: u64,
+    // address liveliness of the bond owner, 0 - 10000
+    pub liveliness: u64,
+}
+
+// callable through cpi, an asset is bonded at most once and its asset usage points to the bond
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey)]
+pub struct GetBondStatus<'info> {
+    /// CHECK: closed when the bond is closed, checked in handler
+    #[account(
+        seeds=[asset_id.as_ref()],
+        bump,
+    )]
+    pub asset_usage: UncheckedAccount<'info>,
+
+    /// CHECK: may be closed or reused by a later bond, checked in handler
+    pub bond: UncheckedAccount<'info>,
+
+    /// CHECK: bond config recorded in the asset usage, checked in handler
+    pub bond_config: UncheckedAccount<'info>,
+
+    /// CHECK: address of the bond owner, checked in handler
+    pub address_bonds_rewards: UncheckedAccount<'info>,
+}
+
+fn is_open(account: &AccountInfo) -> bool {
+    account.owner == &crate::ID && !account.data_is_empty()
+}
+
+fn not_bonded() -> BondStatus {
+    BondStatus {
+        owner: Pubkey::default(),
+        state: State::Inactive.to_code(),
+        bond_amount: 0,
+        unbond_timestamp: 0,
+        liveliness: 0,
+    }
+}
+
+pub fn get_bond_status(ctx: Context<GetBondStatus>, asset_id: Pubkey) -> Result<BondStatus> {
+    let asset_usage_info = ctx.accounts.asset_usage.to_account_info();
+
+    if !is_open(&asset_usage_info) {
+        return Ok(not_bonded());
+    }
+
+    let asset_usage = AssetUsage::try_deserialize(&mut &asset_usage_info.try_borrow_data()?[..])?;
+
+    require!(
+        ctx.accounts.bond.key() == asset_usage.bond,
+        Errors::AssetIdMismatch
+    );
+
+    let bond_info = ctx.accounts.bond.to_account_info();
+
+    if !is_open(&bond_info) {
+        return Ok(not_bonded());
+    }
+
+    let bond = Bond::try_deserialize(&mut &bond_info.try_borrow_data()?[..])?;
+
+    // the bond id was reused by a bond of another asset
+    if bond.asset_id != asset_id {
+        return Ok(not_bonded());
+    }
+
+    let (bond_config_key, _) = Pubkey::find_program_address(
+        &[
+            BOND_CONFIG_SEED.as_bytes(),
+            &asset_usage.bond_config_index.to_be_bytes(),
+        ],
+        &crate::ID,
+    );
+    require!(
+        ctx.accounts.bond_config.key() == bond_config_key,
+        Errors::BondConfigMismatch
+    );
+    let bond_config =
+        BondConfig::try_deserialize(&mut &ctx.accounts.bond_config.try_borrow_data()?[..])?;
+
+    let (address_bonds_rewards_key, _) = Pubkey::find_program_address(
+        &[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), bond.owner.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        ctx.accounts.address_bonds_rewards.key() == address_bonds_rewards_key,
+        Errors::WrongValue
+    );
+    let address_bonds_rewards = AddressBondsRewards::try_deserialize(
+        &mut &ctx.accounts.address_bonds_rewards.try_borrow_data()?[..],
+    )?;
+
+    let current_timestamp = get_current_timestamp()?;
+
+    Ok(BondStatus {
+        owner: bond.owner,
+        state: bond.state,
+        bond_amount: bond.bond_amount,
+        unbond_timestamp: get_unbond_timestamp(
+            bond.auto_renew,
+            bond_config.lock_period,
+            current_timestamp,
+            bond.unbond_timestamp,
+        )?,
+        liveliness: compute_address_liveliness(&address_bonds_rewards, current_timestamp)?,
+    })
+}
@@ -0,0 +1,417 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    allocate_slashed_amount, get_current_timestamp, slash, AddressBondsRewards, AssetUsage, Bond,
+    Challenge, Errors, ProgramConfig, RewardsConfig, State, VaultConfig,
+    ADDRESS_BONDS_REWARDS_SEED, BOND_SEED, CHALLENGE_SEED, DEFAULT_CHALLENGE_PERIOD,
+    PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(bond_id:u16)]
+pub struct OpenChallenge<'info> {
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer=authority,
+        seeds=[CHALLENGE_SEED.as_bytes(), bond.key().as_ref()],
+        bump,
+        space=Challenge::INIT_SPACE
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            owner.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        seeds=[bond.asset_id.as_ref()],
+        bump,
+    )]
+    pub asset_usage: Account<'info, AssetUsage>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+        has_one=vault,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint=vault_config.mint_of_token,
+        associated_token::authority=vault_config,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint=mint_of_token_sent.key() == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub mint_of_token_sent: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint=authority_token_account.owner == authority.key() @ Errors::OwnerMismatch,
+        constraint=authority_token_account.mint == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn open_challenge(ctx: Context<OpenChallenge>) -> Result<()> {
+    let challenge_amount = ctx.accounts.program_config.challenge_amount;
+
+    require!(challenge_amount > 0, Errors::ChallengesDisabled);
+
+    let bond = &mut ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Active.to_code(),
+        Errors::BondIsInactive
+    );
+    require!(bond.slash_reason == 0, Errors::BondIsSlashed);
+
+    bond.challenged = 1;
+
+    let vault_config = &mut ctx.accounts.vault_config;
+    vault_config.total_challenge_amount = vault_config
+        .total_challenge_amount
+        .checked_add(challenge_amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    ctx.accounts.challenge.set_inner(Challenge {
+        bump: ctx.bumps.challenge,
+        bond: bond.key(),
+        asset_id: bond.asset_id,
+        challenger: ctx.accounts.authority.key(),
+        amount: challenge_amount,
+        challenge_timestamp: get_current_timestamp()?,
+        padding: [0; 32],
+    });
+
+    // counter-bond transfer to vault
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint_of_token_sent.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+    transfer_checked(
+        cpi_context,
+        challenge_amount,
+        ctx.accounts.mint_of_token_sent.decimals,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bond_id:u16)]
+pub struct ResolveChallenge<'info> {
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close=challenger,
+        seeds=[CHALLENGE_SEED.as_bytes(), bond.key().as_ref()],
+        bump=challenge.bump,
+        has_one=challenger,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+        has_one=vault,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint=vault_config.mint_of_token,
+        associated_token::authority=vault_config,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint=mint_of_token_to_receive.key() == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub mint_of_token_to_receive: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), owner.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            owner.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the challenge
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint=challenger_token_account.owner == challenger.key() @ Errors::OwnerMismatch,
+        constraint=challenger_token_account.mint == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint=program_config.arbiter == authority.key() @ Errors::NotPrivileged,
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool, reason: u8) -> Result<()> {
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        VAULT_CONFIG_SEED.as_bytes(),
+        &[ctx.accounts.vault_config.bump],
+    ]];
+
+    let program_config = &ctx.accounts.program_config;
+    let challenge_amount = ctx.accounts.challenge.amount;
+
+    let vault_config = &mut ctx.accounts.vault_config;
+    vault_config.total_challenge_amount = vault_config
+        .total_challenge_amount
+        .checked_sub(challenge_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    if upheld {
+        let slashed_amount = slash(
+            &mut ctx.accounts.rewards_config,
+            &mut ctx.accounts.vault_config,
+            &mut ctx.accounts.address_bonds_rewards,
+            &mut ctx.accounts.bond,
+            program_config.slash_share,
+            reason,
+        )?;
+
+        // counter-bond and slashed amount go to the challenger
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.challenger_token_account.to_account_info(),
+            mint: ctx.accounts.mint_of_token_to_receive.to_account_info(),
+            authority: ctx.accounts.vault_config.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+            .with_signer(&signer_seeds);
+
+        transfer_checked(
+            cpi_ctx,
+            challenge_amount
+                .checked_add(slashed_amount)
+                .ok_or(Errors::MathOverflow)?,
+            ctx.accounts.mint_of_token_to_receive.decimals,
+        )?;
+    } else {
+        allocate_slashed_amount(
+            &mut ctx.accounts.rewards_config,
+            &mut ctx.accounts.vault_config,
+            program_config.slash_destination,
+            challenge_amount,
+        )?;
+    }
+
+    ctx.accounts.bond.challenged = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bond_id:u16)]
+pub struct ExpireChallenge<'info> {
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close=challenger,
+        seeds=[CHALLENGE_SEED.as_bytes(), bond.key().as_ref()],
+        bump=challenge.bump,
+        has_one=challenger,
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+        has_one=vault,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint=vault_config.mint_of_token,
+        associated_token::authority=vault_config,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint=mint_of_token_to_receive.key() == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub mint_of_token_to_receive: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            owner.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the challenge
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint=challenger_token_account.owner == challenger.key() @ Errors::OwnerMismatch,
+        constraint=challenger_token_account.mint == vault_config.mint_of_token @ Errors::MintMismatch,
+    )]
+    pub challenger_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint=authority.key() == owner.key() || authority.key() == challenger.key() @ Errors::NotPrivileged,
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// a challenge the arbiter left open past the challenge period is dropped, the counter-bond
+// goes back to the challenger and the bond is released
+pub fn expire_challenge(ctx: Context<ExpireChallenge>) -> Result<()> {
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        VAULT_CONFIG_SEED.as_bytes(),
+        &[ctx.accounts.vault_config.bump],
+    ]];
+
+    let challenge_period = match ctx.accounts.program_config.challenge_period {
+        0 => DEFAULT_CHALLENGE_PERIOD,
+        challenge_period => challenge_period,
+    };
+
+    let challenge_deadline = ctx
+        .accounts
+        .challenge
+        .challenge_timestamp
+        .checked_add(challenge_period)
+        .ok_or(Errors::MathOverflow)?;
+
+    require!(
+        get_current_timestamp()? >= challenge_deadline,
+        Errors::ChallengeNotExpired
+    );
+
+    let challenge_amount = ctx.accounts.challenge.amount;
+
+    let vault_config = &mut ctx.accounts.vault_config;
+    vault_config.total_challenge_amount = vault_config
+        .total_challenge_amount
+        .checked_sub(challenge_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.challenger_token_account.to_account_info(),
+        mint: ctx.accounts.mint_of_token_to_receive.to_account_info(),
+        authority: ctx.accounts.vault_config.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts)
+        .with_signer(&signer_seeds);
+
+    transfer_checked(
+        cpi_ctx,
+        challenge_amount,
+        ctx.accounts.mint_of_token_to_receive.decimals,
+    )?;
+
+    ctx.accounts.bond.challenged = 0;
+
+    Ok(())
+}
@@ -5,10 +5,10 @@ use anchor_spl::{
 };
 
 use crate::{
-    compute_bond_score, full_math::MulDiv, get_current_timestamp, update_address_claimable_rewards,
-    AddressBondsRewards, Bond, BondConfig, Errors, RewardsConfig, State, VaultConfig,
-    ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED, MAX_PERCENT, REWARDS_CONFIG_SEED,
-    VAULT_CONFIG_SEED,
+    apply_address_liveliness, expire_bond_liveliness, get_current_timestamp, get_target_bond_id,
+    update_address_claimable_rewards, AddressBondsRewards, Bond, BondConfig, Errors, RewardsConfig,
+    State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED,
+    REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -32,7 +32,7 @@ pub struct ClaimRewards<'info> {
         seeds = [
             BOND_SEED.as_bytes(),
             authority.key().as_ref(),
-            &bond_id.to_le_bytes()
+            &get_target_bond_id(bond_id, address_bonds_rewards.vault_bond_id).to_le_bytes()
         ],
         bump=bond.bump,
 
@@ -95,8 +95,7 @@ pub fn claim_rewards<'a, 'b, 'c: 'info, 'info>(
     ]];
 
     require!(
-        ctx.accounts.address_bonds_rewards.vault_bond_id == bond_id
-            && ctx.accounts.address_bonds_rewards.vault_bond_id != 0,
+        get_target_bond_id(bond_id, ctx.accounts.address_bonds_rewards.vault_bond_id) != 0,
         Errors::VaultBondIdMismatch
     );
 
@@ -113,29 +112,32 @@ pub fn claim_rewards<'a, 'b, 'c: 'info, 'info>(
         &mut ctx.accounts.address_bonds_rewards,
     )?;
 
+    // open counter-bonds belong to the challengers
+    let available_amount = ctx
+        .accounts
+        .vault
+        .amount
+        .checked_sub(ctx.accounts.vault_config.total_challenge_amount)
+        .ok_or(Errors::NotEnoughBalance)?;
+
     require!(
-        ctx.accounts.vault.amount >= ctx.accounts.address_bonds_rewards.claimable_amount,
+        available_amount >= ctx.accounts.address_bonds_rewards.claimable_amount,
         Errors::NotEnoughBalance
     );
 
     let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
 
-    let actual_claimable_amount;
-
-    let actual_vault_liveliness_score = compute_bond_score(
-        ctx.accounts.bond_config.lock_period,
+    expire_bond_liveliness(
+        address_bonds_rewards,
+        &mut ctx.accounts.bond,
         current_timestamp,
-        ctx.accounts.bond.unbond_timestamp,
-    );
+    )?;
 
-    if actual_vault_liveliness_score >= 95_00u64 {
-        actual_claimable_amount = address_bonds_rewards.claimable_amount;
-    } else {
-        actual_claimable_amount = address_bonds_rewards
-            .claimable_amount
-            .mul_div_floor(actual_vault_liveliness_score, MAX_PERCENT)
-            .unwrap();
-    }
+    let actual_claimable_amount = apply_address_liveliness(
+        &ctx.accounts.rewards_config,
+        address_bonds_rewards,
+        current_timestamp,
+    )?;
 
     address_bonds_rewards.last_update_timestamp = current_timestamp;
 
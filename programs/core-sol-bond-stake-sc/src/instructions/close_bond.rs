@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    update_address_claimable_rewards, AddressBondsRewards, AssetUsage, Bond, Errors, RewardsConfig,
+    State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_SEED, REWARDS_CONFIG_SEED,
+    VAULT_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(bond_id:u16)]
+pub struct CloseBond<'info> {
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        close=authority,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            authority.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    // closed with the bond, the asset can be bonded again
+    #[account(
+        mut,
+        close=authority,
+        seeds=[bond.asset_id.as_ref()],
+        bump,
+        constraint=asset_usage.bond == bond.key() @ Errors::AssetIdMismatch,
+    )]
+    pub asset_usage: Box<Account<'info, AssetUsage>>,
+
+    #[account(
+        mut,
+        constraint=bond.owner == authority.key() @ Errors::OwnerMismatch,
+        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn close_bond(ctx: Context<CloseBond>, bond_id: u16) -> Result<()> {
+    require!(
+        ctx.accounts.bond.state == State::Inactive.to_code(),
+        Errors::BondIsActive
+    );
+
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+    // the vault bond can only go once it is the last open bond, unsynced bonds may be open
+    if address_bonds_rewards.vault_bond_id == bond_id {
+        require!(
+            address_bonds_rewards.synced_index == address_bonds_rewards.current_index
+                && address_bonds_rewards.open_bonds <= 1,
+            Errors::VaultBondCannotBeClosed
+        );
+        address_bonds_rewards.vault_bond_id = 0;
+    }
+
+    // bonds closed before they are synced were never counted and are skipped by the sync
+    if ctx.accounts.bond.counted == 1 {
+        address_bonds_rewards.open_bonds = address_bonds_rewards
+            .open_bonds
+            .checked_sub(1)
+            .ok_or(Errors::MathUnderflow)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseAddress<'info> {
+    #[account(
+        mut,
+        close=authority,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn close_address(ctx: Context<CloseAddress>) -> Result<()> {
+    update_address_claimable_rewards(
+        &mut ctx.accounts.rewards_config,
+        &ctx.accounts.vault_config,
+        &mut ctx.accounts.address_bonds_rewards,
+    )?;
+
+    let address_bonds_rewards = &ctx.accounts.address_bonds_rewards;
+
+    // a reinitialized address restarts its bond ids, every earlier bond must be closed
+    require!(
+        address_bonds_rewards.synced_index == address_bonds_rewards.current_index
+            && address_bonds_rewards.open_bonds == 0
+            && address_bonds_rewards.address_total_bond_amount == 0,
+        Errors::AddressHasOpenBonds
+    );
+
+    require!(
+        address_bonds_rewards.claimable_amount == 0,
+        Errors::UnclaimedRewards
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    expire_bond_liveliness, get_current_timestamp, AddressBondsRewards, Bond,
+    ADDRESS_BONDS_REWARDS_SEED, BOND_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey, bond_id: u16)]
+pub struct ExpireBond<'info> {
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), address.as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            address.as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+    )]
+    pub bond: Account<'info, Bond>,
+}
+
+// counts a bond past its unbond timestamp with zero remaining lock time, bonds that are
+// not expired are left as they are
+pub fn expire_bond(ctx: Context<ExpireBond>) -> Result<()> {
+    expire_bond_liveliness(
+        &mut ctx.accounts.address_bonds_rewards,
+        &mut ctx.accounts.bond,
+        get_current_timestamp()?,
+    )
+}
@@ -40,7 +40,14 @@ pub fn initialize_address<'info>(ctx: Context<InitializeAddress<'info>>) -> Resu
             address_rewards_per_share: ctx.accounts.rewards_config.rewards_per_share,
             claimable_amount: 0,
             vault_bond_id: 0,
-            padding: [0; 16],
+            open_bonds: 0,
+            keeper: Pubkey::default(),
+            liveliness_unbond_sum: 0,
+            liveliness_amount: 0,
+            liveliness_auto_renew_lock_sum: 0,
+            liveliness_lock_sum: 0,
+            synced_index: 0,
+            padding: [0; 12],
         });
 
     Ok(())
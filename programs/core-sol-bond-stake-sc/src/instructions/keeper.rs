@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{AddressBondsRewards, Errors, ADDRESS_BONDS_REWARDS_SEED};
+
+#[derive(Accounts)]
+pub struct UpdateKeeper<'info> {
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+    )]
+    pub authority: Signer<'info>,
+}
+
+// default pubkey removes the keeper
+pub fn update_keeper(ctx: Context<UpdateKeeper>, keeper: Pubkey) -> Result<()> {
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+    address_bonds_rewards.keeper = keeper;
+    Ok(())
+}
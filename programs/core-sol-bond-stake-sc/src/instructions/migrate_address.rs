@@ -0,0 +1,64 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{widen_rewards_per_share, AddressBondsRewards, Errors, ADDRESS_BONDS_REWARDS_SEED};
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct MigrateAddress<'info> {
+    /// CHECK: old layout cannot be deserialized, checked in handler
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), address.as_ref()],
+        bump,
+        owner=crate::ID,
+    )]
+    pub address_bonds_rewards: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// grows accounts created with an older layout, new fields are zero filled and the
+// rewards per share is widened to u128
+pub fn migrate_address(ctx: Context<MigrateAddress>) -> Result<()> {
+    let account = &ctx.accounts.address_bonds_rewards;
+
+    require!(
+        account.try_borrow_data()?[..8] == AddressBondsRewards::DISCRIMINATOR,
+        Errors::WrongValue
+    );
+    require!(
+        account.data_len() < AddressBondsRewards::INIT_SPACE,
+        Errors::AlreadyMigrated
+    );
+
+    let rent = Rent::get()?.minimum_balance(AddressBondsRewards::INIT_SPACE);
+    let lamports = account.lamports();
+
+    if rent > lamports {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account.to_account_info(),
+            },
+        );
+
+        transfer(cpi_ctx, rent - lamports)?;
+    }
+
+    account.realloc(AddressBondsRewards::INIT_SPACE, true)?;
+
+    widen_rewards_per_share(
+        &mut account.try_borrow_mut_data()?,
+        AddressBondsRewards::REWARDS_PER_SHARE_OFFSET,
+    );
+
+    Ok(())
+}
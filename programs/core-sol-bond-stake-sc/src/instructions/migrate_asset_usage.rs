@@ -0,0 +1,96 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{AssetUsage, Bond, BondConfig, Errors, BOND_CONFIG_SEED, BOND_SEED};
+
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey, bond_config_index: u8, bond_id: u16)]
+pub struct MigrateAssetUsage<'info> {
+    /// CHECK: old layout cannot be deserialized, checked in handler
+    #[account(
+        mut,
+        seeds=[asset_id.as_ref()],
+        bump,
+        owner=crate::ID,
+    )]
+    pub asset_usage: UncheckedAccount<'info>,
+
+    #[account(
+        seeds=[BOND_CONFIG_SEED.as_bytes(),&bond_config_index.to_be_bytes()],
+        bump=bond_config.bump,
+    )]
+    pub bond_config: Account<'info, BondConfig>,
+
+    #[account(
+        seeds = [
+            BOND_SEED.as_bytes(),
+            bond.owner.as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+        constraint=bond.asset_id == asset_id @ Errors::AssetIdMismatch,
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// grows marker accounts created with the empty layout and points them to their bond
+pub fn migrate_asset_usage(
+    ctx: Context<MigrateAssetUsage>,
+    asset_id: Pubkey,
+    bond_id: u16,
+    nonce: u64,
+) -> Result<()> {
+    let account = &ctx.accounts.asset_usage;
+
+    require!(
+        account.try_borrow_data()?[..8] == AssetUsage::DISCRIMINATOR,
+        Errors::WrongValue
+    );
+    require!(
+        account.data_len() < AssetUsage::INIT_SPACE,
+        Errors::AlreadyMigrated
+    );
+
+    // the asset must come from the tree of the given bond config
+    require!(
+        get_asset_id(&ctx.accounts.bond_config.merkle_tree, nonce) == asset_id,
+        Errors::AssetIdMismatch
+    );
+
+    let rent = Rent::get()?.minimum_balance(AssetUsage::INIT_SPACE);
+    let lamports = account.lamports();
+
+    if rent > lamports {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account.to_account_info(),
+            },
+        );
+
+        transfer(cpi_ctx, rent - lamports)?;
+    }
+
+    account.realloc(AssetUsage::INIT_SPACE, false)?;
+
+    AssetUsage {
+        bond: ctx.accounts.bond.key(),
+        owner: ctx.accounts.bond.owner,
+        bond_id,
+        bond_config_index: ctx.accounts.bond_config.index,
+        padding: [0; 32],
+    }
+    .try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{widen_rewards_per_share, Errors, RewardsConfig, REWARDS_CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct MigrateRewardsConfig<'info> {
+    /// CHECK: old layout cannot be deserialized, checked in handler
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump,
+        owner=crate::ID,
+    )]
+    pub rewards_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// grows the rewards config to the u128 rewards per share layout
+pub fn migrate_rewards_config(ctx: Context<MigrateRewardsConfig>) -> Result<()> {
+    let account = &ctx.accounts.rewards_config;
+
+    require!(
+        account.try_borrow_data()?[..8] == RewardsConfig::DISCRIMINATOR,
+        Errors::WrongValue
+    );
+    require!(
+        account.data_len() < RewardsConfig::INIT_SPACE,
+        Errors::AlreadyMigrated
+    );
+
+    let rent = Rent::get()?.minimum_balance(RewardsConfig::INIT_SPACE);
+    let lamports = account.lamports();
+
+    if rent > lamports {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: account.to_account_info(),
+            },
+        );
+
+        transfer(cpi_ctx, rent - lamports)?;
+    }
+
+    account.realloc(RewardsConfig::INIT_SPACE, true)?;
+
+    widen_rewards_per_share(
+        &mut account.try_borrow_mut_data()?,
+        RewardsConfig::REWARDS_PER_SHARE_OFFSET,
+    );
+
+    Ok(())
+}
@@ -3,12 +3,32 @@ pub use admin::*;
 
 pub mod bond;
 pub use bond::*;
+pub mod bond_many;
+pub use bond_many::*;
 pub mod renew;
 pub use renew::*;
 pub mod topup;
 pub use topup::*;
 pub mod withdraw;
 pub use withdraw::*;
+pub mod partial_withdraw;
+pub use partial_withdraw::*;
+pub mod unbond;
+pub use unbond::*;
+pub mod close_bond;
+pub use close_bond::*;
+pub mod keeper;
+pub use keeper::*;
+pub mod migrate_address;
+pub use migrate_address::*;
+pub mod migrate_asset_usage;
+pub use migrate_asset_usage::*;
+pub mod migrate_rewards_config;
+pub use migrate_rewards_config::*;
+pub mod sync_bond;
+pub use sync_bond::*;
+pub mod expire_bond;
+pub use expire_bond::*;
 pub mod stake_rewards;
 pub use stake_rewards::*;
 pub mod claim_rewards;
@@ -17,3 +37,11 @@ pub mod initialize_address;
 pub use initialize_address::*;
 pub mod vault_address_update;
 pub use vault_address_update::*;
+pub mod slash_bond;
+pub use slash_bond::*;
+pub mod challenge;
+pub use challenge::*;
+pub mod views;
+pub use views::*;
+pub mod bond_status;
+pub use bond_status::*;
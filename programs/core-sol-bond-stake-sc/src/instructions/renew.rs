@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    get_current_timestamp, update_address_claimable_rewards, AddressBondsRewards, Bond, BondConfig,
-    Errors, RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
-    BOND_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+    add_bond_liveliness, get_current_timestamp, remove_bond_liveliness,
+    update_address_claimable_rewards, AddressBondsRewards, AssetUsage, Bond, BondConfig, Errors,
+    ProgramConfig, RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
+    BOND_SEED, PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -15,6 +16,12 @@ pub struct Renew<'info> {
     )]
     pub bond_config: Account<'info, BondConfig>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         mut,
         seeds=[REWARDS_CONFIG_SEED.as_bytes()],
@@ -32,7 +39,7 @@ pub struct Renew<'info> {
 
     #[account(
         mut,
-        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), owner.key().as_ref()],
         bump=address_bonds_rewards.bump,
     )]
     pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
@@ -41,7 +48,7 @@ pub struct Renew<'info> {
         mut,
         seeds = [
             BOND_SEED.as_bytes(),
-            authority.key().as_ref(),
+            owner.key().as_ref(),
             &bond_id.to_le_bytes()
         ],
         bump=bond.bump,
@@ -49,10 +56,26 @@ pub struct Renew<'info> {
     )]
     pub bond: Account<'info, Bond>,
 
+    // the lock period comes from the bond config the bond was created under
+    #[account(
+        seeds=[bond.asset_id.as_ref()],
+        bump,
+        constraint=asset_usage.bond == bond.key() @ Errors::AssetIdMismatch,
+        constraint=asset_usage.bond_config_index == bond_config.index @ Errors::BondConfigMismatch,
+    )]
+    pub asset_usage: Box<Account<'info, AssetUsage>>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+        constraint=address_bonds_rewards.address==owner.key() @Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // owner or the keeper set by the owner
     #[account(
         mut,
-        constraint=bond.owner == authority.key() @ Errors::OwnerMismatch,
-        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+        constraint=authority.key() == owner.key() || authority.key() == address_bonds_rewards.keeper @ Errors::NotPrivileged,
     )]
     pub authority: Signer<'info>,
 }
@@ -77,8 +100,76 @@ pub fn renew(ctx: Context<Renew>) -> Result<()> {
 
     address_bonds_rewards.last_update_timestamp = current_timestamp;
 
-    bond.unbond_timestamp = current_timestamp + ctx.accounts.bond_config.lock_period;
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
+
+    bond.unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
+    bond.bond_timestamp = current_timestamp;
+
+    add_bond_liveliness(address_bonds_rewards, bond, current_timestamp)?;
+
+    Ok(())
+}
+
+pub fn enable_auto_renew(ctx: Context<Renew>) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.owner.key(),
+        Errors::OwnerMismatch
+    );
+
+    let current_timestamp = get_current_timestamp()?;
+
+    let bond = &mut ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Active.to_code(),
+        Errors::BondIsInactive
+    );
+
+    remove_bond_liveliness(&mut ctx.accounts.address_bonds_rewards, bond)?;
+
+    bond.auto_renew = 1;
+
+    add_bond_liveliness(
+        &mut ctx.accounts.address_bonds_rewards,
+        bond,
+        current_timestamp,
+    )?;
+
+    Ok(())
+}
+
+pub fn disable_auto_renew(ctx: Context<Renew>) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.owner.key(),
+        Errors::OwnerMismatch
+    );
+
+    let current_timestamp = get_current_timestamp()?;
+
+    let bond = &mut ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Active.to_code(),
+        Errors::BondIsInactive
+    );
+    require!(bond.auto_renew == 1, Errors::AutoRenewDisabled);
+
+    remove_bond_liveliness(&mut ctx.accounts.address_bonds_rewards, bond)?;
+
+    // final lock period starts now
+    bond.auto_renew = 0;
+    bond.unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
     bond.bond_timestamp = current_timestamp;
 
+    add_bond_liveliness(
+        &mut ctx.accounts.address_bonds_rewards,
+        bond,
+        current_timestamp,
+    )?;
+
     Ok(())
 }
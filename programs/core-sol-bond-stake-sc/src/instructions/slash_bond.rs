@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    allocate_slashed_amount, slash, AddressBondsRewards, Bond, Errors, ProgramConfig,
+    RewardsConfig, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_SEED, PROGRAM_CONFIG_SEED,
+    REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(bond_id:u16)]
+pub struct SlashBond<'info> {
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), owner.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            owner.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        constraint=program_config.slashing_authority == authority.key() @ Errors::NotPrivileged,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn slash_bond(ctx: Context<SlashBond>, reason: u8) -> Result<()> {
+    require!(ctx.accounts.bond.challenged == 0, Errors::BondIsChallenged);
+
+    let program_config = &ctx.accounts.program_config;
+
+    let slashed_amount = slash(
+        &mut ctx.accounts.rewards_config,
+        &mut ctx.accounts.vault_config,
+        &mut ctx.accounts.address_bonds_rewards,
+        &mut ctx.accounts.bond,
+        program_config.slash_share,
+        reason,
+    )?;
+
+    allocate_slashed_amount(
+        &mut ctx.accounts.rewards_config,
+        &mut ctx.accounts.vault_config,
+        program_config.slash_destination,
+        slashed_amount,
+    )?;
+
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    compute_bond_score, full_math::MulDiv, get_current_timestamp, update_address_claimable_rewards,
-    AddressBondsRewards, Bond, BondConfig, Errors, RewardsConfig, State, VaultConfig,
-    ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED, MAX_PERCENT, REWARDS_CONFIG_SEED,
-    VAULT_CONFIG_SEED,
+    add_bond_liveliness, apply_address_liveliness, expire_bond_liveliness, get_current_timestamp,
+    get_target_bond_id, remove_bond_liveliness, update_address_claimable_rewards,
+    AddressBondsRewards, AssetUsage, Bond, BondConfig, Errors, ProgramConfig, RewardsConfig, State,
+    VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED, PROGRAM_CONFIG_SEED,
+    REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -12,7 +13,7 @@ use crate::{
 pub struct StakeRewards<'info> {
     #[account(
         mut,
-        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), owner.key().as_ref()],
         bump=address_bonds_rewards.bump,
     )]
     pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
@@ -21,20 +22,35 @@ pub struct StakeRewards<'info> {
         mut,
         seeds = [
             BOND_SEED.as_bytes(),
-            authority.key().as_ref(),
-            &bond_id.to_le_bytes()
+            owner.key().as_ref(),
+            &get_target_bond_id(bond_id, address_bonds_rewards.vault_bond_id).to_le_bytes()
         ],
         bump=bond.bump,
 
     )]
     pub bond: Account<'info, Bond>,
 
+    // the lock period comes from the bond config the bond was created under
+    #[account(
+        seeds=[bond.asset_id.as_ref()],
+        bump,
+        constraint=asset_usage.bond == bond.key() @ Errors::AssetIdMismatch,
+        constraint=asset_usage.bond_config_index == bond_config.index @ Errors::BondConfigMismatch,
+    )]
+    pub asset_usage: Box<Account<'info, AssetUsage>>,
+
     #[account(
         seeds=[BOND_CONFIG_SEED.as_bytes(),&bond_config_index.to_be_bytes()],
         bump=bond_config.bump,
     )]
     pub bond_config: Account<'info, BondConfig>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         mut,
         seeds=[REWARDS_CONFIG_SEED.as_bytes()],
@@ -50,10 +66,17 @@ pub struct StakeRewards<'info> {
     )]
     pub vault_config: Account<'info, VaultConfig>,
 
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+        constraint=address_bonds_rewards.address==owner.key() @Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // owner or the keeper set by the owner
     #[account(
         mut,
-        constraint=bond.owner == authority.key() @ Errors::OwnerMismatch,
-        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+        constraint=authority.key() == owner.key() || authority.key() == address_bonds_rewards.keeper @ Errors::NotPrivileged,
     )]
     pub authority: Signer<'info>,
 }
@@ -63,8 +86,7 @@ pub fn stake_rewards<'a, 'b, 'c: 'info, 'info>(
     bond_id: u16,
 ) -> Result<()> {
     require!(
-        ctx.accounts.address_bonds_rewards.vault_bond_id == bond_id
-            && ctx.accounts.address_bonds_rewards.vault_bond_id != 0,
+        get_target_bond_id(bond_id, ctx.accounts.address_bonds_rewards.vault_bond_id) != 0,
         Errors::VaultBondIdMismatch
     );
 
@@ -86,32 +108,38 @@ pub fn stake_rewards<'a, 'b, 'c: 'info, 'info>(
 
     let bond = &mut ctx.accounts.bond;
 
-    let actual_claimable_amount;
+    expire_bond_liveliness(address_bonds_rewards, bond, current_timestamp)?;
 
-    let actual_vault_liveliness_score = compute_bond_score(
-        ctx.accounts.bond_config.lock_period,
+    let actual_claimable_amount = apply_address_liveliness(
+        &ctx.accounts.rewards_config,
+        address_bonds_rewards,
         current_timestamp,
-        bond.unbond_timestamp,
-    );
+    )?;
 
-    if actual_vault_liveliness_score >= 95_00u64 {
-        actual_claimable_amount = address_bonds_rewards.claimable_amount;
-    } else {
-        actual_claimable_amount = address_bonds_rewards
-            .claimable_amount
-            .mul_div_floor(actual_vault_liveliness_score, MAX_PERCENT)
-            .unwrap();
-    }
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
 
-    bond.unbond_timestamp = current_timestamp + ctx.accounts.bond_config.lock_period;
+    bond.unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
     bond.bond_timestamp = current_timestamp;
-    bond.bond_amount += &actual_claimable_amount;
+    bond.bond_amount = bond
+        .bond_amount
+        .checked_add(actual_claimable_amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    add_bond_liveliness(address_bonds_rewards, bond, current_timestamp)?;
 
-    vault_config.total_bond_amount += &actual_claimable_amount;
+    vault_config.total_bond_amount = vault_config
+        .total_bond_amount
+        .checked_add(actual_claimable_amount)
+        .ok_or(Errors::MathOverflow)?;
 
     address_bonds_rewards.claimable_amount = 0;
 
-    address_bonds_rewards.address_total_bond_amount += actual_claimable_amount;
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_add(actual_claimable_amount)
+        .ok_or(Errors::MathOverflow)?;
     address_bonds_rewards.last_update_timestamp = current_timestamp;
 
     Ok(())
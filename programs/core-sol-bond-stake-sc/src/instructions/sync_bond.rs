@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    add_bond_liveliness, get_current_timestamp, AddressBondsRewards, Bond, Errors,
+    ADDRESS_BONDS_REWARDS_SEED, BOND_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct SyncBond<'info> {
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), address.as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    /// CHECK: closed bonds are skipped, checked in handler
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            address.as_ref(),
+            &address_bonds_rewards.synced_index.wrapping_add(1).to_le_bytes()
+        ],
+        bump,
+    )]
+    pub bond: UncheckedAccount<'info>,
+}
+
+// counts bonds created before open bond and liveliness tracking, one bond id at a time in
+// creation order
+pub fn sync_bond(ctx: Context<SyncBond>) -> Result<()> {
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+    require!(
+        address_bonds_rewards.synced_index < address_bonds_rewards.current_index,
+        Errors::AlreadyMigrated
+    );
+
+    let account = &ctx.accounts.bond;
+
+    if account.owner == &crate::ID && !account.data_is_empty() {
+        let mut bond = Bond::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+
+        if bond.counted == 0 {
+            bond.counted = 1;
+
+            address_bonds_rewards.open_bonds = address_bonds_rewards
+                .open_bonds
+                .checked_add(1)
+                .ok_or(Errors::MathOverflow)?;
+
+            add_bond_liveliness(address_bonds_rewards, &mut bond, get_current_timestamp()?)?;
+
+            bond.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    address_bonds_rewards.synced_index += 1;
+
+    Ok(())
+}
@@ -5,9 +5,10 @@ use anchor_spl::{
 };
 
 use crate::{
-    get_current_timestamp, update_address_claimable_rewards, AddressBondsRewards, Bond, BondConfig,
-    Errors, RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
-    BOND_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+    add_bond_liveliness, get_current_timestamp, get_target_bond_id, remove_bond_liveliness,
+    update_address_claimable_rewards, AddressBondsRewards, Bond, BondConfig, Errors, ProgramConfig,
+    RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED,
+    PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -27,6 +28,12 @@ pub struct TopUp<'info> {
     )]
     pub bond_config: Account<'info, BondConfig>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         mut,
         seeds=[REWARDS_CONFIG_SEED.as_bytes()],
@@ -45,7 +52,7 @@ pub struct TopUp<'info> {
         seeds = [
             BOND_SEED.as_bytes(),
             authority.key().as_ref(),
-            &bond_id.to_le_bytes()
+            &get_target_bond_id(bond_id, address_bonds_rewards.vault_bond_id).to_le_bytes()
         ],
         bump=bond.bump,
 
@@ -99,8 +106,7 @@ pub fn top_up<'a, 'b, 'c: 'info, 'info>(
     );
 
     require!(
-        ctx.accounts.address_bonds_rewards.vault_bond_id == bond_id
-            && ctx.accounts.address_bonds_rewards.vault_bond_id != 0,
+        get_target_bond_id(bond_id, ctx.accounts.address_bonds_rewards.vault_bond_id) != 0,
         Errors::VaultBondIdMismatch
     );
 
@@ -114,16 +120,31 @@ pub fn top_up<'a, 'b, 'c: 'info, 'info>(
 
     let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
 
-    address_bonds_rewards.address_total_bond_amount += amount;
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
 
     address_bonds_rewards.last_update_timestamp = current_timestamp;
 
     let vault_config = &mut ctx.accounts.vault_config;
-    vault_config.total_bond_amount += amount;
+    vault_config.total_bond_amount = vault_config
+        .total_bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
 
-    bond.unbond_timestamp = current_timestamp + ctx.accounts.bond_config.lock_period;
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
+
+    bond.unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
     bond.bond_timestamp = current_timestamp;
-    bond.bond_amount += amount;
+    bond.bond_amount = bond
+        .bond_amount
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    add_bond_liveliness(address_bonds_rewards, bond, current_timestamp)?;
 
     // transfer amount to vault
 
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    add_bond_liveliness, full_math::MulDiv, get_current_timestamp, get_unbond_timestamp,
+    remove_bond_liveliness, update_address_claimable_rewards, AddressBondsRewards, Bond,
+    BondConfig, Errors, ProgramConfig, RewardsConfig, State, VaultConfig,
+    ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED, BOND_SEED, MAX_PERCENT, PROGRAM_CONFIG_SEED,
+    REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+};
+
+#[derive(Accounts)]
+#[instruction(bond_config_index:u8,bond_id:u16)]
+pub struct Unbond<'info> {
+    #[account(
+        seeds=[BOND_CONFIG_SEED.as_bytes(),&bond_config_index.to_be_bytes()],
+        bump=bond_config.bump,
+    )]
+    pub bond_config: Account<'info, BondConfig>,
+
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        mut,
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        mut,
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BOND_SEED.as_bytes(),
+            authority.key().as_ref(),
+            &bond_id.to_le_bytes()
+        ],
+        bump=bond.bump,
+
+    )]
+    pub bond: Account<'info, Bond>,
+
+    #[account(
+        mut,
+        constraint=bond.owner == authority.key() @ Errors::OwnerMismatch,
+        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn start_unbond(ctx: Context<Unbond>) -> Result<()> {
+    let bond_config = &ctx.accounts.bond_config;
+
+    require!(bond_config.cooldown_period > 0, Errors::WrongValue);
+
+    let bond = &mut ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Active.to_code(),
+        Errors::BondIsInactive
+    );
+    require!(bond.challenged == 0, Errors::BondIsChallenged);
+
+    let current_timestamp = get_current_timestamp()?;
+
+    update_address_claimable_rewards(
+        &mut ctx.accounts.rewards_config,
+        &ctx.accounts.vault_config,
+        &mut ctx.accounts.address_bonds_rewards,
+    )?;
+
+    // bond stops accruing rewards from here on
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_sub(bond.bond_amount)
+        .ok_or(Errors::MathUnderflow)?;
+    address_bonds_rewards.last_update_timestamp = current_timestamp;
+
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
+
+    let mut penalty = 0u64;
+
+    let unbond_timestamp = get_unbond_timestamp(
+        bond.auto_renew,
+        bond_config.lock_period,
+        current_timestamp,
+        bond.unbond_timestamp,
+    )?;
+
+    if unbond_timestamp >= current_timestamp {
+        penalty = bond
+            .bond_amount
+            .mul_div_floor(bond_config.withdraw_penalty, MAX_PERCENT)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    let vault_config = &mut ctx.accounts.vault_config;
+
+    vault_config.total_bond_amount = vault_config
+        .total_bond_amount
+        .checked_sub(bond.bond_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    // charged by complete_withdraw, cancel_unbond drops it
+    bond.unbond_penalty = penalty;
+    bond.state = State::Unbonding.to_code();
+    bond.unbond_timestamp = current_timestamp;
+    bond.cooldown_end_timestamp = current_timestamp
+        .checked_add(bond_config.cooldown_period)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(())
+}
+
+pub fn cancel_unbond(ctx: Context<Unbond>) -> Result<()> {
+    let bond = &mut ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Unbonding.to_code(),
+        Errors::BondIsNotUnbonding
+    );
+
+    let current_timestamp = get_current_timestamp()?;
+
+    update_address_claimable_rewards(
+        &mut ctx.accounts.rewards_config,
+        &ctx.accounts.vault_config,
+        &mut ctx.accounts.address_bonds_rewards,
+    )?;
+
+    let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_add(bond.bond_amount)
+        .ok_or(Errors::MathOverflow)?;
+    address_bonds_rewards.last_update_timestamp = current_timestamp;
+
+    ctx.accounts.vault_config.total_bond_amount = ctx
+        .accounts
+        .vault_config
+        .total_bond_amount
+        .checked_add(bond.bond_amount)
+        .ok_or(Errors::MathOverflow)?;
+
+    bond.state = State::Active.to_code();
+    bond.bond_timestamp = current_timestamp;
+    bond.unbond_timestamp = current_timestamp
+        .checked_add(ctx.accounts.bond_config.lock_period)
+        .ok_or(Errors::MathOverflow)?;
+    bond.cooldown_end_timestamp = 0;
+    bond.unbond_penalty = 0;
+
+    add_bond_liveliness(
+        &mut ctx.accounts.address_bonds_rewards,
+        bond,
+        current_timestamp,
+    )?;
+
+    Ok(())
+}
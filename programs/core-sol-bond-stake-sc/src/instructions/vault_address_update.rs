@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 use mpl_bubblegum::utils::get_asset_id;
 
 use crate::{
-    AddressBondsRewards, Bond, BondConfig, Errors, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
-    BOND_SEED,
+    AddressBondsRewards, Bond, BondConfig, Errors, ProgramConfig, ADDRESS_BONDS_REWARDS_SEED,
+    BOND_CONFIG_SEED, BOND_SEED, PROGRAM_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -11,7 +11,7 @@ use crate::{
 pub struct VaultAddressUpdate<'info> {
     #[account(
         mut,
-        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), owner.key().as_ref()],
         bump=address_bonds_rewards.bump,
     )]
     pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
@@ -20,7 +20,7 @@ pub struct VaultAddressUpdate<'info> {
         mut,
         seeds = [
             BOND_SEED.as_bytes(),
-            authority.key().as_ref(),
+            owner.key().as_ref(),
             &bond_id.to_le_bytes()
         ],
         bump=bond.bump,
@@ -34,10 +34,23 @@ pub struct VaultAddressUpdate<'info> {
     )]
     pub bond_config: Account<'info, BondConfig>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: bond owner
+    #[account(
+        constraint=bond.owner == owner.key() @ Errors::OwnerMismatch,
+        constraint=address_bonds_rewards.address==owner.key() @Errors::OwnerMismatch,
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // owner or the keeper set by the owner
     #[account(
         mut,
-        constraint=bond.owner == authority.key() @ Errors::OwnerMismatch,
-        constraint=address_bonds_rewards.address==authority.key() @Errors::OwnerMismatch,
+        constraint=authority.key() == owner.key() || authority.key() == address_bonds_rewards.keeper @ Errors::NotPrivileged,
     )]
     pub authority: Signer<'info>,
 
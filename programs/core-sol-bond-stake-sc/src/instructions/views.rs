@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    apply_address_liveliness, compute_address_liveliness, get_current_timestamp,
+    update_address_claimable_rewards, AddressBondsRewards, RewardsConfig, State, VaultConfig,
+    ADDRESS_BONDS_REWARDS_SEED, MAX_PERCENT, REWARDS_CONFIG_SEED, SLOTS_IN_YEAR, VAULT_CONFIG_SEED,
+};
+
+// read only, meant to be simulated
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct ViewAddress<'info> {
+    #[account(
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(
+        seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), address.as_ref()],
+        bump=address_bonds_rewards.bump,
+    )]
+    pub address_bonds_rewards: Box<Account<'info, AddressBondsRewards>>,
+}
+
+#[derive(Accounts)]
+pub struct ViewRewards<'info> {
+    #[account(
+        seeds=[REWARDS_CONFIG_SEED.as_bytes()],
+        bump=rewards_config.bump,
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        seeds=[VAULT_CONFIG_SEED.as_bytes()],
+        bump=vault_config.bump,
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+}
+
+// amount claim_rewards would pay out now, rewards are accrued on copies of the accounts
+pub fn get_claimable_rewards(ctx: Context<ViewAddress>) -> Result<u64> {
+    let mut rewards_config = ctx.accounts.rewards_config.clone();
+    let mut address_bonds_rewards = ctx.accounts.address_bonds_rewards.clone();
+
+    update_address_claimable_rewards(
+        &mut rewards_config,
+        &ctx.accounts.vault_config,
+        &mut address_bonds_rewards,
+    )?;
+
+    apply_address_liveliness(
+        &rewards_config,
+        &address_bonds_rewards,
+        get_current_timestamp()?,
+    )
+}
+
+pub fn get_address_liveliness(ctx: Context<ViewAddress>) -> Result<u64> {
+    compute_address_liveliness(
+        &ctx.accounts.address_bonds_rewards,
+        get_current_timestamp()?,
+    )
+}
+
+// yearly rewards over the total bond amount, 0 - 10000 per 100%, capped by max apr
+pub fn get_current_apr(ctx: Context<ViewRewards>) -> Result<u64> {
+    let rewards_config = &ctx.accounts.rewards_config;
+    let total_bond_amount = ctx.accounts.vault_config.total_bond_amount;
+
+    if rewards_config.rewards_state != State::Active.to_code() || total_bond_amount == 0 {
+        return Ok(0);
+    }
+
+    let apr = rewards_config.rewards_per_slot as u128 * SLOTS_IN_YEAR as u128 * MAX_PERCENT as u128
+        / total_bond_amount as u128;
+    let apr = core::cmp::min(apr, u64::MAX as u128) as u64;
+
+    if rewards_config.max_apr > 0 {
+        Ok(core::cmp::min(apr, rewards_config.max_apr))
+    } else {
+        Ok(apr)
+    }
+}
@@ -3,11 +3,15 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
 };
+use mpl_bubblegum::utils::get_asset_id;
+use spl_account_compression::{program::SplAccountCompression, Noop};
 
 use crate::{
-    get_current_timestamp, update_address_claimable_rewards, AddressBondsRewards, Bond, BondConfig,
-    Errors, RewardsConfig, State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, BOND_CONFIG_SEED,
-    BOND_SEED, MAX_PERCENT, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
+    delegate_asset, full_math::MulDiv, get_current_timestamp, get_unbond_timestamp,
+    remove_bond_liveliness, transfer_asset, update_address_claimable_rewards, AddressBondsRewards,
+    AssetMode, Bond, BondConfig, BubblegumAccounts, Errors, LeafArgs, ProgramConfig, RewardsConfig,
+    State, VaultConfig, ADDRESS_BONDS_REWARDS_SEED, ASSET_AUTHORITY_SEED, BOND_CONFIG_SEED,
+    BOND_SEED, MAX_PERCENT, PROGRAM_CONFIG_SEED, REWARDS_CONFIG_SEED, VAULT_CONFIG_SEED,
 };
 
 #[derive(Accounts)]
@@ -19,6 +23,12 @@ pub struct Withdraw<'info> {
     )]
     pub bond_config: Account<'info, BondConfig>,
 
+    #[account(
+        seeds=[PROGRAM_CONFIG_SEED.as_bytes()],
+        bump=program_config.bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         mut,
         seeds=[ADDRESS_BONDS_REWARDS_SEED.as_bytes(), authority.key().as_ref()],
@@ -84,15 +94,38 @@ pub struct Withdraw<'info> {
     system_program: Program<'info, System>,
     token_program: Program<'info, Token>,
     associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked against the bond asset id
+    #[account(mut)]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: checked by bubblegum
+    pub tree_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pda
+    #[account(
+        seeds=[ASSET_AUTHORITY_SEED.as_bytes()],
+        bump,
+    )]
+    pub asset_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: bubblegum program
+    #[account(address=mpl_bubblegum::ID)]
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+
+    pub log_wrapper: Option<Program<'info, Noop>>,
+
+    pub compression_program: Option<Program<'info, SplAccountCompression>>,
 }
 
 pub fn withdraw<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    leaf_args: Option<LeafArgs>,
 ) -> Result<()> {
-    let signer_seeds: [&[&[u8]]; 1] = [&[
-        VAULT_CONFIG_SEED.as_bytes(),
-        &[ctx.accounts.vault_config.bump],
-    ]];
+    require!(
+        ctx.accounts.bond_config.cooldown_period == 0,
+        Errors::UnbondingRequired
+    );
 
     let bond_config = &ctx.accounts.bond_config;
     let vault_config = &mut ctx.accounts.vault_config;
@@ -103,6 +136,7 @@ pub fn withdraw<'a, 'b, 'c: 'info, 'info>(
         bond.state == State::Active.to_code(),
         Errors::BondIsInactive
     );
+    require!(bond.challenged == 0, Errors::BondIsChallenged);
     let current_timestamp = get_current_timestamp()?;
 
     update_address_claimable_rewards(
@@ -113,17 +147,154 @@ pub fn withdraw<'a, 'b, 'c: 'info, 'info>(
 
     let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
 
-    address_bonds_rewards.address_total_bond_amount -= bond.bond_amount;
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_sub(bond.bond_amount)
+        .ok_or(Errors::MathUnderflow)?;
     address_bonds_rewards.last_update_timestamp = current_timestamp;
 
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
+
     let mut penalty = 0u64;
 
-    if bond.unbond_timestamp >= current_timestamp {
-        penalty = bond.bond_amount * bond_config.withdraw_penalty / MAX_PERCENT;
+    let unbond_timestamp = get_unbond_timestamp(
+        bond.auto_renew,
+        bond_config.lock_period,
+        current_timestamp,
+        bond.unbond_timestamp,
+    )?;
+
+    if unbond_timestamp >= current_timestamp {
+        penalty = bond
+            .bond_amount
+            .mul_div_floor(bond_config.withdraw_penalty, MAX_PERCENT)
+            .ok_or(Errors::MathOverflow)?;
     }
 
-    vault_config.total_penalized_amount += penalty;
-    vault_config.total_bond_amount -= bond.bond_amount;
+    vault_config.total_penalized_amount = vault_config
+        .total_penalized_amount
+        .checked_add(penalty)
+        .ok_or(Errors::MathOverflow)?;
+    vault_config.total_bond_amount = vault_config
+        .total_bond_amount
+        .checked_sub(bond.bond_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    let amount = bond
+        .bond_amount
+        .checked_sub(penalty)
+        .ok_or(Errors::MathUnderflow)?;
+
+    release_bond(&ctx, amount, leaf_args)?;
+
+    let bond = &mut ctx.accounts.bond;
+
+    bond.state = State::Inactive.to_code();
+    bond.unbond_timestamp = current_timestamp;
+    bond.bond_amount = 0;
+
+    Ok(())
+}
+
+pub fn complete_withdraw<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    leaf_args: Option<LeafArgs>,
+) -> Result<()> {
+    let bond = &ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Unbonding.to_code(),
+        Errors::BondIsNotUnbonding
+    );
+    require!(bond.challenged == 0, Errors::BondIsChallenged);
+
+    let current_timestamp = get_current_timestamp()?;
+
+    require!(
+        current_timestamp >= bond.cooldown_end_timestamp,
+        Errors::CooldownNotOver
+    );
+
+    // totals were settled in start_unbond, the penalty is only charged now
+    let penalty = bond.unbond_penalty;
+    let amount = bond
+        .bond_amount
+        .checked_sub(penalty)
+        .ok_or(Errors::MathUnderflow)?;
+
+    release_bond(&ctx, amount, leaf_args)?;
+
+    let vault_config = &mut ctx.accounts.vault_config;
+
+    vault_config.total_penalized_amount = vault_config
+        .total_penalized_amount
+        .checked_add(penalty)
+        .ok_or(Errors::MathOverflow)?;
+
+    let bond = &mut ctx.accounts.bond;
+
+    bond.state = State::Inactive.to_code();
+    bond.bond_amount = 0;
+    bond.unbond_penalty = 0;
+
+    Ok(())
+}
+
+// principal only, no penalty and no rewards accounting
+pub fn emergency_withdraw<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    leaf_args: Option<LeafArgs>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.program_config.emergency_state == State::Active.to_code(),
+        Errors::EmergencyStateInactive
+    );
+
+    let bond = &ctx.accounts.bond;
+
+    require!(
+        bond.state == State::Active.to_code() || bond.state == State::Unbonding.to_code(),
+        Errors::BondIsInactive
+    );
+    require!(bond.challenged == 0, Errors::BondIsChallenged);
+
+    let amount = bond.bond_amount;
+
+    // unbonding bonds were already removed from the totals
+    if bond.state == State::Active.to_code() {
+        let address_bonds_rewards = &mut ctx.accounts.address_bonds_rewards;
+
+        remove_bond_liveliness(address_bonds_rewards, bond)?;
+
+        address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+            .address_total_bond_amount
+            .saturating_sub(amount);
+
+        let vault_config = &mut ctx.accounts.vault_config;
+        vault_config.total_bond_amount = vault_config.total_bond_amount.saturating_sub(amount);
+    }
+
+    release_bond(&ctx, amount, leaf_args)?;
+
+    let bond = &mut ctx.accounts.bond;
+
+    bond.state = State::Inactive.to_code();
+    bond.unbond_timestamp = get_current_timestamp()?;
+    bond.bond_amount = 0;
+    bond.unbond_penalty = 0;
+
+    Ok(())
+}
+
+fn release_bond<'a, 'b, 'c: 'info, 'info>(
+    ctx: &Context<'a, 'b, 'c, 'info, Withdraw<'info>>,
+    amount: u64,
+    leaf_args: Option<LeafArgs>,
+) -> Result<()> {
+    let signer_seeds: [&[&[u8]]; 1] = [&[
+        VAULT_CONFIG_SEED.as_bytes(),
+        &[ctx.accounts.vault_config.bump],
+    ]];
 
     // transfer bond to authority
 
@@ -139,13 +310,76 @@ pub fn withdraw<'a, 'b, 'c: 'info, 'info>(
 
     transfer_checked(
         cpi_ctx,
-        bond.bond_amount - penalty,
+        amount,
         ctx.accounts.mint_of_token_to_receive.decimals,
     )?;
 
-    bond.state = State::Inactive.to_code();
-    bond.unbond_timestamp = current_timestamp;
-    bond.bond_amount = 0;
+    let asset_mode = ctx.accounts.bond.asset_mode;
+
+    // return escrowed leaf to the owner, or hand the delegation back to the owner
+    if asset_mode == AssetMode::Escrow.to_code() || asset_mode == AssetMode::Delegate.to_code() {
+        let (
+            Some(leaf_args),
+            Some(merkle_tree),
+            Some(tree_config),
+            Some(asset_authority),
+            Some(bubblegum_program),
+            Some(log_wrapper),
+            Some(compression_program),
+        ) = (
+            leaf_args,
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.tree_config,
+            &ctx.accounts.asset_authority,
+            &ctx.accounts.bubblegum_program,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+        )
+        else {
+            return err!(Errors::MissingAccounts);
+        };
+
+        require!(
+            get_asset_id(&merkle_tree.key(), leaf_args.nonce) == ctx.accounts.bond.asset_id,
+            Errors::AssetIdMismatch
+        );
+
+        let bubblegum_accounts = BubblegumAccounts {
+            bubblegum_program,
+            tree_config,
+            merkle_tree,
+            log_wrapper,
+            compression_program,
+            system_program: &ctx.accounts.system_program,
+        };
+
+        if asset_mode == AssetMode::Escrow.to_code() {
+            let asset_authority_seeds: [&[&[u8]]; 1] = [&[
+                ASSET_AUTHORITY_SEED.as_bytes(),
+                &[ctx.bumps.asset_authority],
+            ]];
+
+            transfer_asset(
+                &bubblegum_accounts,
+                asset_authority,
+                asset_authority,
+                &ctx.accounts.authority,
+                ctx.remaining_accounts,
+                &leaf_args,
+                &asset_authority_seeds,
+            )?;
+        } else {
+            // the owner as delegate clears the delegation
+            delegate_asset(
+                &bubblegum_accounts,
+                &ctx.accounts.authority,
+                asset_authority,
+                &ctx.accounts.authority,
+                ctx.remaining_accounts,
+                &leaf_args,
+            )?;
+        }
+    }
 
     Ok(())
 }
@@ -1,16 +1,17 @@
 use anchor_lang::prelude::*;
 
 mod instructions;
+pub use instructions::{BondManyArgs, BondStatus};
 use instructions::*;
-mod states;
+pub mod states;
 use states::*;
-mod utils;
+pub mod utils;
 use utils::*;
-mod libraries;
+pub mod libraries;
 use libraries::*;
-mod constants;
+pub mod constants;
 use constants::*;
-mod errors;
+pub mod errors;
 use errors::*;
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -107,6 +108,91 @@ pub mod core_sol_bond_stake_sc {
         instructions::update_withdraw_penalty(ctx, withdraw_penalty)
     }
 
+    pub fn update_asset_mode(
+        ctx: Context<UpdateBondConfig>,
+        _index: u8,
+        asset_mode: u8,
+    ) -> Result<()> {
+        instructions::update_asset_mode(ctx, asset_mode)
+    }
+
+    pub fn update_cooldown_period(
+        ctx: Context<UpdateBondConfig>,
+        _index: u8,
+        cooldown_period: u64,
+    ) -> Result<()> {
+        instructions::update_cooldown_period(ctx, cooldown_period)
+    }
+
+    pub fn update_pause_flags(
+        ctx: Context<UpdateBondConfig>,
+        _index: u8,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::update_pause_flags(ctx, pause_flags)
+    }
+
+    // Program config
+
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        slashing_authority: Pubkey,
+        slash_share: u64,
+    ) -> Result<()> {
+        instructions::initialize_program_config(ctx, slashing_authority, slash_share)
+    }
+
+    pub fn update_slashing_authority(
+        ctx: Context<UpdateProgramConfig>,
+        slashing_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::update_slashing_authority(ctx, slashing_authority)
+    }
+
+    pub fn update_slash_share(ctx: Context<UpdateProgramConfig>, slash_share: u64) -> Result<()> {
+        instructions::update_slash_share(ctx, slash_share)
+    }
+
+    pub fn update_slash_destination(
+        ctx: Context<UpdateProgramConfig>,
+        slash_destination: u8,
+    ) -> Result<()> {
+        instructions::update_slash_destination(ctx, slash_destination)
+    }
+
+    pub fn update_arbiter(ctx: Context<UpdateProgramConfig>, arbiter: Pubkey) -> Result<()> {
+        instructions::update_arbiter(ctx, arbiter)
+    }
+
+    pub fn update_challenge_amount(
+        ctx: Context<UpdateProgramConfig>,
+        challenge_amount: u64,
+    ) -> Result<()> {
+        instructions::update_challenge_amount(ctx, challenge_amount)
+    }
+
+    pub fn update_challenge_period(
+        ctx: Context<UpdateProgramConfig>,
+        challenge_period: u64,
+    ) -> Result<()> {
+        instructions::update_challenge_period(ctx, challenge_period)
+    }
+
+    pub fn set_emergency_state_active(ctx: Context<UpdateProgramConfig>) -> Result<()> {
+        instructions::update_emergency_state(ctx, State::Active.to_code())
+    }
+
+    pub fn set_emergency_state_inactive(ctx: Context<UpdateProgramConfig>) -> Result<()> {
+        instructions::update_emergency_state(ctx, State::Inactive.to_code())
+    }
+
+    pub fn update_global_pause_flags(
+        ctx: Context<UpdateProgramConfig>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::update_global_pause_flags(ctx, pause_flags)
+    }
+
     //Rewards config
 
     pub fn set_rewards_state_active(ctx: Context<UpdateRewardsConfig>) -> Result<()> {
@@ -128,6 +214,20 @@ pub mod core_sol_bond_stake_sc {
         instructions::update_max_apr(ctx, max_apr)
     }
 
+    pub fn update_liveliness_curve(
+        ctx: Context<UpdateRewardsConfig>,
+        liveliness_curve: u8,
+        liveliness_threshold: u64,
+        liveliness_curve_param: u64,
+    ) -> Result<()> {
+        instructions::update_liveliness_curve(
+            ctx,
+            liveliness_curve,
+            liveliness_threshold,
+            liveliness_curve_param,
+        )
+    }
+
     pub fn add_rewards(ctx: Context<RewardsContext>, amount: u64) -> Result<()> {
         instructions::add_rewards(ctx, amount)
     }
@@ -142,6 +242,36 @@ pub mod core_sol_bond_stake_sc {
         instructions::initialize_address(ctx)
     }
 
+    pub fn migrate_address(ctx: Context<MigrateAddress>, _address: Pubkey) -> Result<()> {
+        instructions::migrate_address(ctx)
+    }
+
+    pub fn migrate_asset_usage(
+        ctx: Context<MigrateAssetUsage>,
+        asset_id: Pubkey,
+        _bond_config_index: u8,
+        bond_id: u16,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::migrate_asset_usage(ctx, asset_id, bond_id, nonce)
+    }
+
+    pub fn migrate_rewards_config(ctx: Context<MigrateRewardsConfig>) -> Result<()> {
+        instructions::migrate_rewards_config(ctx)
+    }
+
+    pub fn sync_bond(ctx: Context<SyncBond>, _address: Pubkey) -> Result<()> {
+        instructions::sync_bond(ctx)
+    }
+
+    pub fn expire_bond(ctx: Context<ExpireBond>, _address: Pubkey, _bond_id: u16) -> Result<()> {
+        instructions::expire_bond(ctx)
+    }
+
+    pub fn update_keeper(ctx: Context<UpdateKeeper>, keeper: Pubkey) -> Result<()> {
+        instructions::update_keeper(ctx, keeper)
+    }
+
     pub fn bond<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'info, 'info, BondContext<'info>>,
         _bond_config_index: u8,
@@ -152,13 +282,43 @@ pub mod core_sol_bond_stake_sc {
         data_hash: [u8; 32],
         creator_hash: [u8; 32],
     ) -> Result<()> {
+        // an inactive bond config takes no new bonds, existing bonds follow the pause flags
         require!(
             ctx.accounts.bond_config.bond_state == State::Active.to_code(),
             Errors::ProgramIsPaused
         );
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Bond
+            ),
+            Errors::ProgramIsPaused
+        );
         instructions::bond(ctx, bond_id, amount, nonce, root, data_hash, creator_hash)
     }
 
+    pub fn bond_many<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, BondMany<'info>>,
+        _bond_config_index: u8,
+        root: [u8; 32],
+        assets: Vec<BondManyArgs>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            Errors::ProgramIsPaused
+        );
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Bond
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::bond_many(ctx, root, assets)
+    }
+
     pub fn update_vault_bond(
         ctx: Context<VaultAddressUpdate>,
         _bond_config_index: u8,
@@ -166,7 +326,11 @@ pub mod core_sol_bond_stake_sc {
         nonce: u64,
     ) -> Result<()> {
         require!(
-            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::UpdateVaultBond
+            ),
             Errors::ProgramIsPaused
         );
         instructions::update_vault_bond(ctx, bond_id, nonce)
@@ -174,22 +338,135 @@ pub mod core_sol_bond_stake_sc {
 
     pub fn renew(ctx: Context<Renew>, _bond_config_index: u8, _bond_id: u16) -> Result<()> {
         require!(
-            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Renew
+            ),
             Errors::ProgramIsPaused
         );
         instructions::renew(ctx)
     }
 
+    pub fn enable_auto_renew(
+        ctx: Context<Renew>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+    ) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Renew
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::enable_auto_renew(ctx)
+    }
+
+    pub fn disable_auto_renew(
+        ctx: Context<Renew>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+    ) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Renew
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::disable_auto_renew(ctx)
+    }
+
     pub fn withdraw<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'info, 'info, Withdraw<'info>>,
         _bond_config_index: u8,
         _bond_id: u16,
+        leaf_args: Option<LeafArgs>,
     ) -> Result<()> {
         require!(
-            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Withdraw
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::withdraw(ctx, leaf_args)
+    }
+
+    // no pause check, emergency state is checked in the instruction
+    pub fn emergency_withdraw<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, Withdraw<'info>>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+        leaf_args: Option<LeafArgs>,
+    ) -> Result<()> {
+        instructions::emergency_withdraw(ctx, leaf_args)
+    }
+
+    pub fn start_unbond(ctx: Context<Unbond>, _bond_config_index: u8, _bond_id: u16) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Withdraw
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::start_unbond(ctx)
+    }
+
+    pub fn complete_withdraw<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, Withdraw<'info>>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+        leaf_args: Option<LeafArgs>,
+    ) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Withdraw
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::complete_withdraw(ctx, leaf_args)
+    }
+
+    pub fn cancel_unbond(
+        ctx: Context<Unbond>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+    ) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Renew
+            ),
+            Errors::ProgramIsPaused
+        );
+        instructions::cancel_unbond(ctx)
+    }
+
+    pub fn partial_withdraw<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, PartialWithdraw<'info>>,
+        _bond_config_index: u8,
+        _bond_id: u16,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::Withdraw
+            ),
             Errors::ProgramIsPaused
         );
-        instructions::withdraw(ctx)
+        instructions::partial_withdraw(ctx, amount)
     }
 
     pub fn top_up<'a, 'b, 'c: 'info, 'info>(
@@ -199,12 +476,24 @@ pub mod core_sol_bond_stake_sc {
         amount: u64,
     ) -> Result<()> {
         require!(
-            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::TopUp
+            ),
             Errors::ProgramIsPaused
         );
         instructions::top_up(ctx, bond_id, amount)
     }
 
+    pub fn close_bond(ctx: Context<CloseBond>, bond_id: u16) -> Result<()> {
+        instructions::close_bond(ctx, bond_id)
+    }
+
+    pub fn close_address(ctx: Context<CloseAddress>) -> Result<()> {
+        instructions::close_address(ctx)
+    }
+
     // Rewards
 
     pub fn stake_rewards<'a, 'b, 'c: 'info, 'info>(
@@ -213,7 +502,11 @@ pub mod core_sol_bond_stake_sc {
         bond_id: u16,
     ) -> Result<()> {
         require!(
-            ctx.accounts.bond_config.bond_state == State::Active.to_code(),
+            !is_paused(
+                &ctx.accounts.bond_config,
+                &ctx.accounts.program_config,
+                PauseFlag::StakeRewards
+            ),
             Errors::ProgramIsPaused
         );
         require!(
@@ -234,4 +527,45 @@ pub mod core_sol_bond_stake_sc {
         );
         instructions::claim_rewards(ctx, bond_id)
     }
+
+    // Slashing
+
+    pub fn slash_bond(ctx: Context<SlashBond>, _bond_id: u16, reason: u8) -> Result<()> {
+        instructions::slash_bond(ctx, reason)
+    }
+
+    pub fn open_challenge(ctx: Context<OpenChallenge>, _bond_id: u16) -> Result<()> {
+        instructions::open_challenge(ctx)
+    }
+
+    pub fn resolve_challenge(
+        ctx: Context<ResolveChallenge>,
+        _bond_id: u16,
+        upheld: bool,
+        reason: u8,
+    ) -> Result<()> {
+        instructions::resolve_challenge(ctx, upheld, reason)
+    }
+
+    pub fn expire_challenge(ctx: Context<ExpireChallenge>, _bond_id: u16) -> Result<()> {
+        instructions::expire_challenge(ctx)
+    }
+
+    // Views
+
+    pub fn get_claimable_rewards(ctx: Context<ViewAddress>, _address: Pubkey) -> Result<u64> {
+        instructions::get_claimable_rewards(ctx)
+    }
+
+    pub fn get_address_liveliness(ctx: Context<ViewAddress>, _address: Pubkey) -> Result<u64> {
+        instructions::get_address_liveliness(ctx)
+    }
+
+    pub fn get_current_apr(ctx: Context<ViewRewards>) -> Result<u64> {
+        instructions::get_current_apr(ctx)
+    }
+
+    pub fn get_bond_status(ctx: Context<GetBondStatus>, asset_id: Pubkey) -> Result<BondStatus> {
+        instructions::get_bond_status(ctx, asset_id)
+    }
 }
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::{DelegateCpiBuilder, TransferCpiBuilder};
+
+use crate::LeafArgs;
+
+pub struct BubblegumAccounts<'b, 'info> {
+    pub bubblegum_program: &'b AccountInfo<'info>,
+    pub tree_config: &'b AccountInfo<'info>,
+    pub merkle_tree: &'b AccountInfo<'info>,
+    pub log_wrapper: &'b AccountInfo<'info>,
+    pub compression_program: &'b AccountInfo<'info>,
+    pub system_program: &'b AccountInfo<'info>,
+}
+
+pub fn delegate_asset<'b, 'info>(
+    accounts: &BubblegumAccounts<'b, 'info>,
+    leaf_owner: &'b AccountInfo<'info>,
+    previous_leaf_delegate: &'b AccountInfo<'info>,
+    new_leaf_delegate: &'b AccountInfo<'info>,
+    proof: &'b [AccountInfo<'info>],
+    leaf_args: &LeafArgs,
+) -> Result<()> {
+    let proof = proof
+        .iter()
+        .map(|account| (account, account.is_signer, account.is_writable))
+        .collect::<Vec<_>>();
+
+    DelegateCpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(leaf_owner)
+        .previous_leaf_delegate(previous_leaf_delegate)
+        .new_leaf_delegate(new_leaf_delegate)
+        .merkle_tree(accounts.merkle_tree)
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .root(leaf_args.root)
+        .data_hash(leaf_args.data_hash)
+        .creator_hash(leaf_args.creator_hash)
+        .nonce(leaf_args.nonce)
+        .index(leaf_args.nonce as u32)
+        .add_remaining_accounts(&proof)
+        .invoke()?;
+
+    Ok(())
+}
+
+pub fn transfer_asset<'b, 'info>(
+    accounts: &BubblegumAccounts<'b, 'info>,
+    leaf_owner: &'b AccountInfo<'info>,
+    leaf_delegate: &'b AccountInfo<'info>,
+    new_leaf_owner: &'b AccountInfo<'info>,
+    proof: &'b [AccountInfo<'info>],
+    leaf_args: &LeafArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let proof = proof
+        .iter()
+        .map(|account| (account, account.is_signer, account.is_writable))
+        .collect::<Vec<_>>();
+
+    TransferCpiBuilder::new(accounts.bubblegum_program)
+        .tree_config(accounts.tree_config)
+        .leaf_owner(leaf_owner, true)
+        .leaf_delegate(leaf_delegate, false)
+        .new_leaf_owner(new_leaf_owner)
+        .merkle_tree(accounts.merkle_tree)
+        .log_wrapper(accounts.log_wrapper)
+        .compression_program(accounts.compression_program)
+        .system_program(accounts.system_program)
+        .root(leaf_args.root)
+        .data_hash(leaf_args.data_hash)
+        .creator_hash(leaf_args.creator_hash)
+        .nonce(leaf_args.nonce)
+        .index(leaf_args.nonce as u32)
+        .add_remaining_accounts(&proof)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
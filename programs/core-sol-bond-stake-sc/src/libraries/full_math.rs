@@ -3,7 +3,7 @@
 //! and supports U128 operations.
 //!
 
-use crate::libraries::u128::U128;
+use crate::libraries::u128::{U128, U256};
 
 /// Trait for calculating `val * num / denom` with different rounding modes and overflow
 /// protection.
@@ -59,6 +59,41 @@ impl MulDiv for u64 {
     }
 }
 
+impl MulDiv for U128 {
+    type Output = U128;
+
+    fn mul_div_floor(self, num: Self, denom: Self) -> Option<Self::Output> {
+        assert_ne!(denom, U128::default());
+        let r =
+            (U256::from(self.as_u128()) * U256::from(num.as_u128())) / U256::from(denom.as_u128());
+        if r > U256::from(u128::MAX) {
+            None
+        } else {
+            Some(U128::from(r.as_u128()))
+        }
+    }
+
+    fn mul_div_ceil(self, num: Self, denom: Self) -> Option<Self::Output> {
+        assert_ne!(denom, U128::default());
+        let r = (U256::from(self.as_u128()) * U256::from(num.as_u128())
+            + U256::from((denom - 1).as_u128()))
+            / U256::from(denom.as_u128());
+        if r > U256::from(u128::MAX) {
+            None
+        } else {
+            Some(U128::from(r.as_u128()))
+        }
+    }
+
+    fn to_underflow_u64(self) -> u64 {
+        if self > U128::from(u64::MAX) {
+            u64::MAX
+        } else {
+            self.as_u64()
+        }
+    }
+}
+
 #[cfg(test)]
 mod muldiv_u64_tests {
     use super::*;
@@ -117,7 +152,7 @@ mod muldiv_u64_tests {
 mod muldiv_u128_tests {
     use super::*;
 
-    use quickcheck::{Arbitrary, Gen};
+    use quickcheck::{quickcheck, Arbitrary, Gen};
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     struct NonZero(U128);
@@ -143,4 +178,49 @@ mod muldiv_u128_tests {
             }
         }
     }
+    quickcheck! {
+        fn scale_floor(val: U128, num: U128, den: NonZero) -> bool {
+            let res = val.mul_div_floor(num, den.0);
+
+            let expected = (U256::from(val.as_u128()) * U256::from(num.as_u128()))
+                / U256::from(den.0.as_u128());
+
+            if expected > U256::from(u128::MAX) {
+                res.is_none()
+            } else {
+                res == Some(U128::from(expected.as_u128()))
+            }
+        }
+    }
+
+    quickcheck! {
+        fn scale_ceil(val: U128, num: U128, den: NonZero) -> bool {
+            let res = val.mul_div_ceil(num, den.0);
+
+            let mut expected = (U256::from(val.as_u128()) * U256::from(num.as_u128()))
+                / U256::from(den.0.as_u128());
+            let expected_rem = (U256::from(val.as_u128()) * U256::from(num.as_u128()))
+                % U256::from(den.0.as_u128());
+
+            if expected_rem != U256::default() {
+                expected += U256::from(1)
+            }
+
+            if expected > U256::from(u128::MAX) {
+                res.is_none()
+            } else {
+                res == Some(U128::from(expected.as_u128()))
+            }
+        }
+    }
+
+    #[test]
+    fn scale_full_range() {
+        let max = U128::from(u128::MAX);
+
+        assert_eq!(max.mul_div_floor(max, max), Some(max));
+        assert_eq!(max.mul_div_ceil(max - 1, max), Some(max - 1));
+        assert_eq!(max.mul_div_floor(max, max - 1), None);
+        assert_eq!(max.to_underflow_u64(), u64::MAX);
+    }
 }
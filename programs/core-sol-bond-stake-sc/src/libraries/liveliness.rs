@@ -0,0 +1,377 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    full_math::MulDiv, AddressBondsRewards, Bond, Errors, LivelinessCurve, RewardsConfig, State,
+    DEFAULT_LIVELINESS_THRESHOLD, MAX_PERCENT,
+};
+
+// active bonds keep unbond_timestamp - bond_timestamp equal to their lock period
+fn get_bond_lock_period(bond: &Bond) -> u64 {
+    bond.unbond_timestamp.saturating_sub(bond.bond_timestamp)
+}
+
+// bonds created before liveliness tracking only enter the sums once synced, bonds already
+// past their unbond timestamp only add their lock period
+pub fn add_bond_liveliness(
+    address_bonds_rewards: &mut AddressBondsRewards,
+    bond: &mut Bond,
+    current_timestamp: u64,
+) -> Result<()> {
+    if bond.state != State::Active.to_code() || bond.counted == 0 {
+        return Ok(());
+    }
+
+    bond.lock_expired = 0;
+
+    let amount = bond.bond_amount as u128;
+    let weighted_lock_period = amount * get_bond_lock_period(bond) as u128;
+
+    address_bonds_rewards.liveliness_lock_sum = address_bonds_rewards
+        .liveliness_lock_sum
+        .checked_add(weighted_lock_period)
+        .ok_or(Errors::MathOverflow)?;
+
+    if bond.auto_renew == 1 {
+        address_bonds_rewards.liveliness_auto_renew_lock_sum = address_bonds_rewards
+            .liveliness_auto_renew_lock_sum
+            .checked_add(weighted_lock_period)
+            .ok_or(Errors::MathOverflow)?;
+    } else if bond.unbond_timestamp <= current_timestamp {
+        bond.lock_expired = 1;
+    } else {
+        address_bonds_rewards.liveliness_unbond_sum = address_bonds_rewards
+            .liveliness_unbond_sum
+            .checked_add(amount * bond.unbond_timestamp as u128)
+            .ok_or(Errors::MathOverflow)?;
+        address_bonds_rewards.liveliness_amount = address_bonds_rewards
+            .liveliness_amount
+            .checked_add(bond.bond_amount)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+pub fn remove_bond_liveliness(
+    address_bonds_rewards: &mut AddressBondsRewards,
+    bond: &Bond,
+) -> Result<()> {
+    if bond.state != State::Active.to_code() || bond.counted == 0 {
+        return Ok(());
+    }
+
+    let amount = bond.bond_amount as u128;
+    let weighted_lock_period = amount * get_bond_lock_period(bond) as u128;
+
+    address_bonds_rewards.liveliness_lock_sum = address_bonds_rewards
+        .liveliness_lock_sum
+        .checked_sub(weighted_lock_period)
+        .ok_or(Errors::MathUnderflow)?;
+
+    if bond.auto_renew == 1 {
+        address_bonds_rewards.liveliness_auto_renew_lock_sum = address_bonds_rewards
+            .liveliness_auto_renew_lock_sum
+            .checked_sub(weighted_lock_period)
+            .ok_or(Errors::MathUnderflow)?;
+    } else if bond.lock_expired == 0 {
+        remove_bond_unbond_timestamp(address_bonds_rewards, bond)?;
+    }
+
+    Ok(())
+}
+
+fn remove_bond_unbond_timestamp(
+    address_bonds_rewards: &mut AddressBondsRewards,
+    bond: &Bond,
+) -> Result<()> {
+    address_bonds_rewards.liveliness_unbond_sum = address_bonds_rewards
+        .liveliness_unbond_sum
+        .checked_sub(bond.bond_amount as u128 * bond.unbond_timestamp as u128)
+        .ok_or(Errors::MathUnderflow)?;
+    address_bonds_rewards.liveliness_amount = address_bonds_rewards
+        .liveliness_amount
+        .checked_sub(bond.bond_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    Ok(())
+}
+
+// drops the remaining lock time of a bond past its unbond timestamp so it counts as zero
+// instead of negative, its lock period stays in the sums
+pub fn expire_bond_liveliness(
+    address_bonds_rewards: &mut AddressBondsRewards,
+    bond: &mut Bond,
+    current_timestamp: u64,
+) -> Result<()> {
+    if bond.state != State::Active.to_code()
+        || bond.counted == 0
+        || bond.auto_renew == 1
+        || bond.lock_expired == 1
+        || bond.unbond_timestamp > current_timestamp
+    {
+        return Ok(());
+    }
+
+    remove_bond_unbond_timestamp(address_bonds_rewards, bond)?;
+
+    bond.lock_expired = 1;
+
+    Ok(())
+}
+
+// stake weighted remaining lock time over stake weighted lock period, 0 - 10000
+// bonds count with zero remaining time once expired through expire_bond_liveliness
+pub fn compute_address_liveliness(
+    address_bonds_rewards: &AddressBondsRewards,
+    current_timestamp: u64,
+) -> Result<u64> {
+    // the sums miss bonds that are not synced yet
+    require!(
+        address_bonds_rewards.synced_index >= address_bonds_rewards.current_index,
+        Errors::AddressNotSynced
+    );
+
+    let lock_sum = address_bonds_rewards.liveliness_lock_sum;
+
+    if lock_sum == 0 {
+        return Ok(0);
+    }
+
+    let remaining = address_bonds_rewards
+        .liveliness_unbond_sum
+        .saturating_sub(address_bonds_rewards.liveliness_amount as u128 * current_timestamp as u128)
+        .checked_add(address_bonds_rewards.liveliness_auto_renew_lock_sum)
+        .ok_or(Errors::MathOverflow)?;
+
+    let liveliness = core::cmp::min(remaining, lock_sum)
+        .checked_mul(MAX_PERCENT as u128)
+        .ok_or(Errors::MathOverflow)?
+        / lock_sum;
+
+    Ok(liveliness as u64)
+}
+
+// share of the claimable rewards paid out for a liveliness score, 0 - 10000
+pub fn compute_liveliness_payout_share(
+    rewards_config: &RewardsConfig,
+    liveliness: u64,
+) -> Result<u64> {
+    // rewards configs created before the curve settings read a zero threshold
+    let threshold = match rewards_config.liveliness_threshold {
+        0 => DEFAULT_LIVELINESS_THRESHOLD,
+        threshold => threshold,
+    };
+
+    if liveliness >= threshold {
+        return Ok(MAX_PERCENT);
+    }
+
+    if rewards_config.liveliness_curve == LivelinessCurve::Step.to_code() {
+        Ok(rewards_config.liveliness_curve_param)
+    } else if rewards_config.liveliness_curve == LivelinessCurve::QuadraticDecay.to_code() {
+        let distance = threshold - liveliness;
+        let decay = distance
+            .checked_mul(distance)
+            .and_then(|square| square.checked_mul(MAX_PERCENT))
+            .ok_or(Errors::MathOverflow)?;
+        let range = threshold
+            .checked_mul(threshold)
+            .ok_or(Errors::MathOverflow)?;
+
+        Ok(MAX_PERCENT - decay / range)
+    } else {
+        Ok(liveliness)
+    }
+}
+
+// claimable amount after applying the rewards config curve to the address liveliness
+pub fn apply_address_liveliness(
+    rewards_config: &RewardsConfig,
+    address_bonds_rewards: &AddressBondsRewards,
+    current_timestamp: u64,
+) -> Result<u64> {
+    let liveliness = compute_address_liveliness(address_bonds_rewards, current_timestamp)?;
+
+    let payout = address_bonds_rewards
+        .claimable_amount
+        .mul_div_floor(
+            compute_liveliness_payout_share(rewards_config, liveliness)?,
+            MAX_PERCENT,
+        )
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(payout)
+}
+
+#[cfg(test)]
+mod liveliness_overflow_tests {
+    use super::*;
+    use crate::states::fixtures::{self, address_bonds_rewards, liveliness_rewards_config};
+
+    fn bond(bond_amount: u64, auto_renew: u8) -> Bond {
+        Bond {
+            unbond_timestamp: 1,
+            bond_amount,
+            auto_renew,
+            ..fixtures::bond()
+        }
+    }
+
+    #[test]
+    fn bond_liveliness_sums_overflow() {
+        let mut address = address_bonds_rewards();
+        address.liveliness_lock_sum = u128::MAX - 1;
+        assert_eq!(
+            add_bond_liveliness(&mut address, &mut bond(1, 1), 0),
+            Ok(())
+        );
+        assert_eq!(address.liveliness_lock_sum, u128::MAX);
+        assert_eq!(
+            add_bond_liveliness(&mut address, &mut bond(1, 1), 0),
+            Err(Errors::MathOverflow.into())
+        );
+
+        let mut address = address_bonds_rewards();
+        address.liveliness_auto_renew_lock_sum = u128::MAX;
+        assert_eq!(
+            add_bond_liveliness(&mut address, &mut bond(1, 1), 0),
+            Err(Errors::MathOverflow.into())
+        );
+
+        let mut address = address_bonds_rewards();
+        address.liveliness_unbond_sum = u128::MAX;
+        assert_eq!(
+            add_bond_liveliness(&mut address, &mut bond(1, 0), 0),
+            Err(Errors::MathOverflow.into())
+        );
+
+        let mut address = address_bonds_rewards();
+        address.liveliness_amount = u64::MAX;
+        assert_eq!(
+            add_bond_liveliness(&mut address, &mut bond(1, 0), 0),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn bond_liveliness_sums_underflow() {
+        let mut address = address_bonds_rewards();
+        assert_eq!(
+            remove_bond_liveliness(&mut address, &bond(1, 1)),
+            Err(Errors::MathUnderflow.into())
+        );
+        assert_eq!(
+            remove_bond_liveliness(&mut address, &bond(1, 0)),
+            Err(Errors::MathUnderflow.into())
+        );
+
+        // bonds not synced yet were never added
+        let mut unsynced = bond(1, 0);
+        unsynced.counted = 0;
+        assert_eq!(add_bond_liveliness(&mut address, &mut unsynced, 0), Ok(()));
+        assert_eq!(remove_bond_liveliness(&mut address, &unsynced), Ok(()));
+        assert_eq!(address.liveliness_lock_sum, 0);
+        assert_eq!(address.liveliness_amount, 0);
+    }
+
+    #[test]
+    fn unsynced_address_liveliness_is_rejected() {
+        let mut address = address_bonds_rewards();
+        address.current_index = 1;
+        assert_eq!(
+            compute_address_liveliness(&address, 0),
+            Err(Errors::AddressNotSynced.into())
+        );
+
+        address.synced_index = 1;
+        assert_eq!(compute_address_liveliness(&address, 0), Ok(0));
+    }
+
+    #[test]
+    fn expired_bonds_count_as_zero_remaining_time() {
+        let year = 365 * 24 * 3600;
+
+        let mut address = address_bonds_rewards();
+        let mut expired = bond(1000, 0);
+        expired.unbond_timestamp = year;
+        let mut fresh = bond(1000, 0);
+        fresh.bond_timestamp = 2 * year;
+        fresh.unbond_timestamp = 3 * year;
+
+        add_bond_liveliness(&mut address, &mut expired, 0).unwrap();
+        add_bond_liveliness(&mut address, &mut fresh, 2 * year).unwrap();
+
+        // the expired bond cancels the fresh one until it is expired
+        assert_eq!(compute_address_liveliness(&address, 2 * year), Ok(0));
+
+        expire_bond_liveliness(&mut address, &mut fresh, 2 * year).unwrap();
+        assert_eq!(fresh.lock_expired, 0);
+        expire_bond_liveliness(&mut address, &mut expired, 2 * year).unwrap();
+        assert_eq!(expired.lock_expired, 1);
+        assert_eq!(
+            compute_address_liveliness(&address, 2 * year),
+            Ok(MAX_PERCENT / 2)
+        );
+
+        // expired bonds leave only their lock period behind
+        remove_bond_liveliness(&mut address, &expired).unwrap();
+        remove_bond_liveliness(&mut address, &fresh).unwrap();
+        assert_eq!(address.liveliness_lock_sum, 0);
+        assert_eq!(address.liveliness_unbond_sum, 0);
+        assert_eq!(address.liveliness_amount, 0);
+
+        // bonds added past their unbond timestamp are expired right away
+        add_bond_liveliness(&mut address, &mut expired, 2 * year).unwrap();
+        assert_eq!(expired.lock_expired, 1);
+        assert_eq!(address.liveliness_amount, 0);
+    }
+
+    #[test]
+    fn address_liveliness_overflow() {
+        let mut address = address_bonds_rewards();
+        address.liveliness_lock_sum = u128::MAX / MAX_PERCENT as u128;
+        address.liveliness_auto_renew_lock_sum = address.liveliness_lock_sum;
+        assert_eq!(compute_address_liveliness(&address, 0), Ok(MAX_PERCENT));
+
+        address.liveliness_lock_sum += 1;
+        address.liveliness_auto_renew_lock_sum += 1;
+        assert_eq!(
+            compute_address_liveliness(&address, 0),
+            Err(Errors::MathOverflow.into())
+        );
+
+        address.liveliness_unbond_sum = 1;
+        address.liveliness_auto_renew_lock_sum = u128::MAX;
+        assert_eq!(
+            compute_address_liveliness(&address, 0),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn payout_share_overflow() {
+        let quadratic = liveliness_rewards_config(LivelinessCurve::QuadraticDecay, MAX_PERCENT, 0);
+        assert_eq!(compute_liveliness_payout_share(&quadratic, 0), Ok(0));
+
+        let quadratic = liveliness_rewards_config(LivelinessCurve::QuadraticDecay, u64::MAX, 0);
+        assert_eq!(
+            compute_liveliness_payout_share(&quadratic, 0),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn applied_liveliness_overflow() {
+        let mut address = address_bonds_rewards();
+        address.claimable_amount = u64::MAX;
+
+        let step = liveliness_rewards_config(LivelinessCurve::Step, MAX_PERCENT, MAX_PERCENT);
+        assert_eq!(apply_address_liveliness(&step, &address, 0), Ok(u64::MAX));
+
+        let step = liveliness_rewards_config(LivelinessCurve::Step, MAX_PERCENT, MAX_PERCENT + 1);
+        assert_eq!(
+            apply_address_liveliness(&step, &address, 0),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+}
@@ -2,3 +2,9 @@ pub mod u128;
 pub mod full_math;
 pub mod rewards;
 pub use rewards::*;
+pub mod bubblegum;
+pub use bubblegum::*;
+pub mod slashing;
+pub use slashing::*;
+pub mod liveliness;
+pub use liveliness::*;
@@ -1,29 +1,38 @@
 use crate::{
-    get_current_slot, AddressBondsRewards, RewardsConfig, State, VaultConfig,
-    DIVISION_SAFETY_CONST, MAX_PERCENT, SLOTS_IN_YEAR,
+    get_current_slot, AddressBondsRewards, Errors, RewardsConfig, State, VaultConfig, MAX_PERCENT,
+    REWARDS_PER_SHARE_PRECISION, SLOTS_IN_YEAR,
 };
 use anchor_lang::prelude::*;
 
-use super::full_math::MulDiv;
+use super::{full_math::MulDiv, u128::U128};
 
-pub fn generate_aggregated_rewards<'a, 'b, 'c: 'info, 'info>(
-    rewards_config: &mut Account<'info, RewardsConfig>,
-    vault_config: &Account<'info, VaultConfig>,
+pub fn generate_aggregated_rewards(
+    rewards_config: &mut RewardsConfig,
+    vault_config: &VaultConfig,
+    current_slot: u64,
 ) -> Result<()> {
     let last_reward_slot = rewards_config.last_reward_slot;
-    let extra_rewards_unbounded = calculate_rewards_since_last_allocation(rewards_config)?;
+    let extra_rewards_unbounded =
+        calculate_rewards_since_last_allocation(rewards_config, current_slot)?;
     let max_apr = rewards_config.max_apr;
 
+    // nothing is distributed over an empty vault, the emissions stay in the reserve
+    if vault_config.total_bond_amount == 0 {
+        return Ok(());
+    }
+
     let extra_rewards: u64;
     if max_apr > 0 {
         let extra_rewards_apr_bonded_per_slot =
-            get_amount_apr_bounded(rewards_config.max_apr, vault_config.total_bond_amount);
-
-        let current_slot = get_current_slot()?;
+            get_amount_apr_bounded(rewards_config.max_apr, vault_config.total_bond_amount)?;
 
-        let slot_diff = current_slot - last_reward_slot;
+        let slot_diff = current_slot
+            .checked_sub(last_reward_slot)
+            .ok_or(Errors::MathUnderflow)?;
 
-        let extra_rewards_apr_bonded = extra_rewards_apr_bonded_per_slot * slot_diff;
+        let extra_rewards_apr_bonded = extra_rewards_apr_bonded_per_slot
+            .checked_mul(slot_diff)
+            .ok_or(Errors::MathOverflow)?;
 
         extra_rewards = core::cmp::min(extra_rewards_unbounded, extra_rewards_apr_bonded);
     } else {
@@ -31,27 +40,40 @@ pub fn generate_aggregated_rewards<'a, 'b, 'c: 'info, 'info>(
     }
 
     if extra_rewards > 0 && extra_rewards <= rewards_config.rewards_reserve {
-        let increment = extra_rewards
-            .mul_div_floor(DIVISION_SAFETY_CONST, vault_config.total_bond_amount)
-            .unwrap();
+        let increment = U128::from(extra_rewards)
+            .mul_div_floor(
+                U128::from(REWARDS_PER_SHARE_PRECISION),
+                U128::from(vault_config.total_bond_amount),
+            )
+            .ok_or(Errors::MathOverflow)?
+            .as_u128();
 
-        rewards_config.rewards_per_share += increment;
+        rewards_config.rewards_per_share = rewards_config
+            .rewards_per_share
+            .checked_add(increment)
+            .ok_or(Errors::MathOverflow)?;
         rewards_config.rewards_reserve -= extra_rewards;
-        rewards_config.accumulated_rewards += extra_rewards;
+        rewards_config.accumulated_rewards = rewards_config
+            .accumulated_rewards
+            .checked_add(extra_rewards)
+            .ok_or(Errors::MathOverflow)?;
     }
 
     Ok(())
 }
 
-pub fn get_amount_apr_bounded(max_apr: u64, amount: u64) -> u64 {
-    amount * max_apr / MAX_PERCENT / SLOTS_IN_YEAR
+pub fn get_amount_apr_bounded(max_apr: u64, amount: u64) -> Result<u64> {
+    let amount_apr_bounded = amount
+        .mul_div_floor(max_apr, MAX_PERCENT)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(amount_apr_bounded / SLOTS_IN_YEAR)
 }
 
-pub fn calculate_rewards_since_last_allocation<'info>(
-    rewards_config: &mut Account<'info, RewardsConfig>,
+pub fn calculate_rewards_since_last_allocation(
+    rewards_config: &mut RewardsConfig,
+    current_slot: u64,
 ) -> Result<u64> {
-    let current_slot = get_current_slot()?;
-
     if rewards_config.rewards_state == State::Inactive.to_code() {
         return Ok(0u64);
     }
@@ -64,39 +86,66 @@ pub fn calculate_rewards_since_last_allocation<'info>(
 
     rewards_config.last_reward_slot = current_slot;
 
-    Ok(rewards_config.rewards_per_slot * slot_diff)
+    let rewards = rewards_config
+        .rewards_per_slot
+        .checked_mul(slot_diff)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(rewards)
 }
 
 pub fn calculate_address_share_in_rewards(
     accumulated_rewards: u64,
-    rewards_per_share: u64,
+    rewards_per_share: u128,
     address_bond_amount: u64,
-    address_rewards_per_share: u64,
+    address_rewards_per_share: u128,
     total_bond_amount: u64,
-) -> u64 {
+) -> Result<u64> {
     if total_bond_amount == 0 {
-        return 0;
+        return Ok(0);
     }
 
     if accumulated_rewards == 0 {
-        return 0;
+        return Ok(0);
     }
 
-    let diff = rewards_per_share - address_rewards_per_share;
+    let diff = rewards_per_share
+        .checked_sub(address_rewards_per_share)
+        .ok_or(Errors::MathUnderflow)?;
+
+    let address_rewards = U128::from(address_bond_amount)
+        .mul_div_floor(U128::from(diff), U128::from(REWARDS_PER_SHARE_PRECISION))
+        .ok_or(Errors::MathOverflow)?;
+
+    require!(
+        address_rewards <= U128::from(u64::MAX),
+        Errors::MathOverflow
+    );
 
-    let address_rewards = address_bond_amount
-        .mul_div_floor(diff, DIVISION_SAFETY_CONST)
-        .unwrap();
+    Ok(address_rewards.as_u64())
+}
 
-    address_rewards
+pub fn update_address_claimable_rewards(
+    rewards_config: &mut RewardsConfig,
+    vault_config: &VaultConfig,
+    address_bonds_rewards: &mut AddressBondsRewards,
+) -> Result<()> {
+    accrue_address_rewards(
+        rewards_config,
+        vault_config,
+        address_bonds_rewards,
+        get_current_slot()?,
+    )
 }
 
-pub fn update_address_claimable_rewards<'info>(
-    rewards_config: &mut Account<'info, RewardsConfig>,
-    vault_config: &Account<'info, VaultConfig>,
-    address_bonds_rewards: &mut Account<'info, AddressBondsRewards>,
+// distributes rewards up to current_slot and moves the address share into claimable_amount
+pub fn accrue_address_rewards(
+    rewards_config: &mut RewardsConfig,
+    vault_config: &VaultConfig,
+    address_bonds_rewards: &mut AddressBondsRewards,
+    current_slot: u64,
 ) -> Result<()> {
-    generate_aggregated_rewards(rewards_config, vault_config)?;
+    generate_aggregated_rewards(rewards_config, vault_config, current_slot)?;
 
     let address_claimable_rewards = calculate_address_share_in_rewards(
         rewards_config.accumulated_rewards,
@@ -104,10 +153,384 @@ pub fn update_address_claimable_rewards<'info>(
         address_bonds_rewards.address_total_bond_amount,
         address_bonds_rewards.address_rewards_per_share,
         vault_config.total_bond_amount,
-    );
+    )?;
 
     address_bonds_rewards.address_rewards_per_share = rewards_config.rewards_per_share;
-    address_bonds_rewards.claimable_amount += address_claimable_rewards;
+    address_bonds_rewards.claimable_amount = address_bonds_rewards
+        .claimable_amount
+        .checked_add(address_claimable_rewards)
+        .ok_or(Errors::MathOverflow)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod rewards_accounting_tests {
+    use super::*;
+    use crate::states::fixtures::{self, address_bonds_rewards, vault_config};
+
+    use quickcheck::{quickcheck, Arbitrary, Gen};
+
+    const USERS: usize = 3;
+    const MIN_BOND_AMOUNT: u64 = 1_000_000;
+
+    // quickcheck integers stay below the generator size, amounts need the full range
+    fn below<G: Gen>(g: &mut G, bound: u64) -> u64 {
+        g.next_u64() % bound
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Action {
+        Bond { user: usize, amount: u64 },
+        Withdraw { user: usize },
+        Claim { user: usize },
+        AddRewards { amount: u64 },
+        Advance { slots: u64 },
+    }
+
+    impl Arbitrary for Action {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            let user = usize::arbitrary(g) % USERS;
+
+            match u8::arbitrary(g) % 5 {
+                0 => Action::Bond {
+                    user,
+                    amount: MIN_BOND_AMOUNT + below(g, 1_000_000_000_000),
+                },
+                1 => Action::Withdraw { user },
+                2 => Action::Claim { user },
+                3 => Action::AddRewards {
+                    amount: below(g, 1_000_000_000_000),
+                },
+                _ => Action::Advance {
+                    slots: 1 + below(g, 1_000),
+                },
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct Config {
+        rewards_per_slot: u64,
+        max_apr: u64,
+        rewards_reserve: u64,
+    }
+
+    impl Arbitrary for Config {
+        fn arbitrary<G: Gen>(g: &mut G) -> Self {
+            Config {
+                rewards_per_slot: below(g, 1_000_000),
+                max_apr: if bool::arbitrary(g) {
+                    0
+                } else {
+                    below(g, 100 * MAX_PERCENT)
+                },
+                rewards_reserve: below(g, 1_000_000_000_000),
+            }
+        }
+    }
+
+    fn rewards_config(config: &Config) -> RewardsConfig {
+        RewardsConfig {
+            rewards_reserve: config.rewards_reserve,
+            rewards_per_slot: config.rewards_per_slot,
+            max_apr: config.max_apr,
+            ..fixtures::rewards_config()
+        }
+    }
+
+    // replays the actions the way the instructions touch the accounts
+    fn simulate(config: Config, actions: Vec<Action>) -> bool {
+        let mut rewards_config = rewards_config(&config);
+        let mut vault_config = vault_config();
+        let mut addresses = vec![address_bonds_rewards(); USERS];
+
+        let mut current_slot = 0u64;
+        let mut deposited = config.rewards_reserve;
+        let mut paid = 0u64;
+
+        for action in actions {
+            match action {
+                Action::Bond { user, amount } => {
+                    let address = &mut addresses[user];
+                    accrue_address_rewards(
+                        &mut rewards_config,
+                        &vault_config,
+                        address,
+                        current_slot,
+                    )
+                    .unwrap();
+
+                    address.address_total_bond_amount += amount;
+                    vault_config.total_bond_amount += amount;
+                }
+                Action::Withdraw { user } => {
+                    let address = &mut addresses[user];
+                    accrue_address_rewards(
+                        &mut rewards_config,
+                        &vault_config,
+                        address,
+                        current_slot,
+                    )
+                    .unwrap();
+
+                    vault_config.total_bond_amount -= address.address_total_bond_amount;
+                    address.address_total_bond_amount = 0;
+                }
+                Action::Claim { user } => {
+                    let address = &mut addresses[user];
+                    accrue_address_rewards(
+                        &mut rewards_config,
+                        &vault_config,
+                        address,
+                        current_slot,
+                    )
+                    .unwrap();
+
+                    paid += address.claimable_amount;
+                    address.claimable_amount = 0;
+                }
+                Action::AddRewards { amount } => {
+                    rewards_config.rewards_reserve += amount;
+                    deposited += amount;
+                }
+                Action::Advance { slots } => {
+                    current_slot += slots;
+                }
+            }
+
+            let claimable: u64 = addresses
+                .iter()
+                .map(|address| address.claimable_amount)
+                .sum();
+
+            if paid > rewards_config.accumulated_rewards
+                || rewards_config.rewards_reserve + rewards_config.accumulated_rewards != deposited
+                || paid + claimable > rewards_config.accumulated_rewards
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    quickcheck! {
+        fn rewards_accounting_holds(config: Config, actions: Vec<Action>) -> bool {
+            simulate(config, actions)
+        }
+    }
+
+    quickcheck! {
+        fn distribution_stays_within_reserve(config: Config, total_bond_amount: u64, slots: u16) -> bool {
+            let mut rewards_config = rewards_config(&config);
+            let mut vault_config = vault_config();
+            vault_config.total_bond_amount = MIN_BOND_AMOUNT + total_bond_amount;
+
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, slots as u64).unwrap();
+
+            rewards_config.accumulated_rewards <= config.rewards_reserve
+                && rewards_config.rewards_reserve + rewards_config.accumulated_rewards
+                    == config.rewards_reserve
+        }
+    }
+
+    fn config(rewards_per_slot: u64, max_apr: u64, rewards_reserve: u64) -> RewardsConfig {
+        rewards_config(&Config {
+            rewards_per_slot,
+            max_apr,
+            rewards_reserve,
+        })
+    }
+
+    #[test]
+    fn empty_vault_keeps_emissions_in_reserve() {
+        let mut rewards_config = config(10, 0, 1_000_000);
+        let mut vault_config = vault_config();
+        let mut address = address_bonds_rewards();
+
+        // rewards are active before the first bond
+        accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 100).unwrap();
+        assert_eq!(rewards_config.last_reward_slot, 100);
+        assert_eq!(rewards_config.rewards_reserve, 1_000_000);
+        assert_eq!(rewards_config.accumulated_rewards, 0);
+
+        address.address_total_bond_amount = MIN_BOND_AMOUNT;
+        vault_config.total_bond_amount = MIN_BOND_AMOUNT;
+
+        accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 150).unwrap();
+        assert_eq!(rewards_config.accumulated_rewards, 500);
+        assert_eq!(address.claimable_amount, 500);
+
+        // the last withdrawer leaves, the next bond is not paid for the empty slots
+        address.address_total_bond_amount = 0;
+        vault_config.total_bond_amount = 0;
+
+        accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 250).unwrap();
+        assert_eq!(rewards_config.last_reward_slot, 250);
+        assert_eq!(rewards_config.rewards_reserve, 1_000_000 - 500);
+        assert_eq!(rewards_config.accumulated_rewards, 500);
+
+        address.address_total_bond_amount = MIN_BOND_AMOUNT;
+        vault_config.total_bond_amount = MIN_BOND_AMOUNT;
+
+        accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 260).unwrap();
+        assert_eq!(rewards_config.accumulated_rewards, 600);
+        assert_eq!(address.claimable_amount, 600);
+    }
+
+    #[test]
+    fn empty_vault_with_max_apr_keeps_emissions_in_reserve() {
+        let mut rewards_config = config(10, MAX_PERCENT, 1_000_000);
+
+        generate_aggregated_rewards(&mut rewards_config, &vault_config(), 100).unwrap();
+        assert_eq!(rewards_config.last_reward_slot, 100);
+        assert_eq!(rewards_config.rewards_reserve, 1_000_000);
+        assert_eq!(rewards_config.rewards_per_share, 0);
+    }
+
+    #[test]
+    fn rewards_since_last_allocation_overflow() {
+        let mut rewards_config = config(u64::MAX / 2, 0, 0);
+        assert_eq!(
+            calculate_rewards_since_last_allocation(&mut rewards_config, 2),
+            Ok(u64::MAX - 1)
+        );
+
+        let mut rewards_config = config(u64::MAX / 2, 0, 0);
+        assert_eq!(
+            calculate_rewards_since_last_allocation(&mut rewards_config, 3),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn amount_apr_bounded_overflow() {
+        assert_eq!(
+            get_amount_apr_bounded(MAX_PERCENT, u64::MAX),
+            Ok(u64::MAX / SLOTS_IN_YEAR)
+        );
+        assert_eq!(
+            get_amount_apr_bounded(MAX_PERCENT + 1, u64::MAX),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn apr_bounded_rewards_overflow() {
+        let mut rewards_config = config(1, MAX_PERCENT, u64::MAX);
+        let mut vault_config = vault_config();
+        vault_config.total_bond_amount = u64::MAX;
+
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, SLOTS_IN_YEAR),
+            Ok(())
+        );
+
+        let mut rewards_config = config(1, MAX_PERCENT, u64::MAX);
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, 2 * SLOTS_IN_YEAR),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn rewards_per_share_overflow() {
+        let mut vault_config = vault_config();
+        vault_config.total_bond_amount = 1;
+
+        // the largest increment still fits
+        let mut rewards_config = config(u64::MAX, 0, u64::MAX);
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, 1),
+            Ok(())
+        );
+        assert_eq!(
+            rewards_config.rewards_per_share,
+            u64::MAX as u128 * REWARDS_PER_SHARE_PRECISION
+        );
+
+        let mut rewards_config = config(1, 0, u64::MAX);
+        rewards_config.rewards_per_share = u128::MAX - REWARDS_PER_SHARE_PRECISION;
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, 1),
+            Ok(())
+        );
+        assert_eq!(rewards_config.rewards_per_share, u128::MAX);
+
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, 2),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn small_emissions_over_large_stake_accrue() {
+        // a billion tokens of 9 decimals bonded, one base unit emitted per slot
+        let mut rewards_config = config(1, 0, 1_000_000);
+        let mut vault_config = vault_config();
+        vault_config.total_bond_amount = 1_000_000_000_000_000_000;
+
+        let mut address = address_bonds_rewards();
+        address.address_total_bond_amount = vault_config.total_bond_amount / 3;
+
+        for slot in 1..=3_000 {
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, slot).unwrap();
+        }
+        assert_eq!(rewards_config.rewards_per_share, 3_000);
+        assert_eq!(rewards_config.accumulated_rewards, 3_000);
+
+        accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 3_000).unwrap();
+        assert_eq!(address.claimable_amount, 999);
+    }
+
+    #[test]
+    fn accumulated_rewards_overflow() {
+        let mut vault_config = vault_config();
+        vault_config.total_bond_amount = 1_000_000_000;
+
+        let mut rewards_config = config(1, 0, 1);
+        rewards_config.accumulated_rewards = u64::MAX;
+
+        assert_eq!(
+            generate_aggregated_rewards(&mut rewards_config, &vault_config, 1),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn address_share_boundaries() {
+        assert_eq!(
+            calculate_address_share_in_rewards(1, 0, 1, 1, 1),
+            Err(Errors::MathUnderflow.into())
+        );
+        assert_eq!(
+            calculate_address_share_in_rewards(1, REWARDS_PER_SHARE_PRECISION, u64::MAX, 0, 1),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            calculate_address_share_in_rewards(1, REWARDS_PER_SHARE_PRECISION * 2, u64::MAX, 0, 1),
+            Err(Errors::MathOverflow.into())
+        );
+        assert_eq!(
+            calculate_address_share_in_rewards(1, u128::MAX, u64::MAX, 0, 1),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn claimable_amount_overflow() {
+        let mut rewards_config = config(1, 0, 1);
+        let mut vault_config = vault_config();
+        vault_config.total_bond_amount = 1;
+
+        let mut address = address_bonds_rewards();
+        address.address_total_bond_amount = 1;
+        address.claimable_amount = u64::MAX;
+
+        assert_eq!(
+            accrue_address_rewards(&mut rewards_config, &vault_config, &mut address, 1),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+}
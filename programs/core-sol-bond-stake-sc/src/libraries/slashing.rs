@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    add_bond_liveliness, full_math::MulDiv, get_current_timestamp, remove_bond_liveliness,
+    update_address_claimable_rewards, AddressBondsRewards, Bond, Errors, RewardsConfig,
+    SlashDestination, State, VaultConfig, MAX_PERCENT,
+};
+
+pub fn slash<'info>(
+    rewards_config: &mut Account<'info, RewardsConfig>,
+    vault_config: &mut Account<'info, VaultConfig>,
+    address_bonds_rewards: &mut Account<'info, AddressBondsRewards>,
+    bond: &mut Account<'info, Bond>,
+    slash_share: u64,
+    reason: u8,
+) -> Result<u64> {
+    require!(reason != 0, Errors::WrongValue);
+    require!(
+        bond.state == State::Active.to_code(),
+        Errors::BondIsInactive
+    );
+    require!(bond.slash_reason == 0, Errors::BondIsSlashed);
+
+    update_address_claimable_rewards(rewards_config, vault_config, address_bonds_rewards)?;
+
+    let slashed_amount = bond
+        .bond_amount
+        .mul_div_floor(slash_share, MAX_PERCENT)
+        .ok_or(Errors::MathOverflow)?;
+
+    remove_bond_liveliness(address_bonds_rewards, bond)?;
+
+    bond.bond_amount = bond
+        .bond_amount
+        .checked_sub(slashed_amount)
+        .ok_or(Errors::MathUnderflow)?;
+    bond.slash_reason = reason;
+
+    add_bond_liveliness(address_bonds_rewards, bond, get_current_timestamp()?)?;
+
+    address_bonds_rewards.address_total_bond_amount = address_bonds_rewards
+        .address_total_bond_amount
+        .checked_sub(slashed_amount)
+        .ok_or(Errors::MathUnderflow)?;
+    vault_config.total_bond_amount = vault_config
+        .total_bond_amount
+        .checked_sub(slashed_amount)
+        .ok_or(Errors::MathUnderflow)?;
+
+    Ok(slashed_amount)
+}
+
+// slashed tokens stay in the vault
+pub fn allocate_slashed_amount<'info>(
+    rewards_config: &mut Account<'info, RewardsConfig>,
+    vault_config: &mut Account<'info, VaultConfig>,
+    slash_destination: u8,
+    amount: u64,
+) -> Result<()> {
+    if slash_destination == SlashDestination::RewardsReserve.to_code() {
+        rewards_config.rewards_reserve = rewards_config
+            .rewards_reserve
+            .checked_add(amount)
+            .ok_or(Errors::MathOverflow)?;
+    } else {
+        vault_config.total_penalized_amount = vault_config
+            .total_penalized_amount
+            .checked_add(amount)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    Ok(())
+}
@@ -5,3 +5,8 @@ use uint::construct_uint;
 construct_uint! {
     pub struct U128(2);
 }
+
+// intermediate for U128 multiply-divide
+construct_uint! {
+    pub struct U256(4);
+}
@@ -7,11 +7,23 @@ pub struct AddressBondsRewards {
     pub address_total_bond_amount: u64,
     pub current_index: u16,
     pub last_update_timestamp: u64,
-    pub address_rewards_per_share: u64,
+    pub address_rewards_per_share: u128,
     pub claimable_amount: u64,
     pub vault_bond_id: u16,
-    pub padding: [u8; 16],
+    pub open_bonds: u16,
+    pub keeper: Pubkey,
+    pub liveliness_unbond_sum: u128,
+    pub liveliness_amount: u64,
+    pub liveliness_auto_renew_lock_sum: u128,
+    pub liveliness_lock_sum: u128,
+    pub synced_index: u16,
+    pub padding: [u8; 12],
 }
 impl Space for AddressBondsRewards {
-    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 2 + 8 + 8 + 8 + 2 + 16;
+    const INIT_SPACE: usize =
+        8 + 1 + 32 + 8 + 2 + 8 + 16 + 8 + 2 + 2 + 32 + 16 + 8 + 16 + 16 + 2 + 12;
+}
+
+impl AddressBondsRewards {
+    pub const REWARDS_PER_SHARE_OFFSET: usize = 8 + 1 + 32 + 8 + 2 + 8;
 }
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
 
+// marks a bonded asset and points back to its bond
 #[account]
-pub struct AssetUsage {}
+pub struct AssetUsage {
+    pub bond: Pubkey,
+    pub owner: Pubkey,
+    pub bond_id: u16,
+    pub bond_config_index: u8,
+    pub padding: [u8; 32],
+}
 impl Space for AssetUsage {
-    const INIT_SPACE: usize = 8;
+    const INIT_SPACE: usize = 8 + 32 + 32 + 2 + 1 + 32;
 }
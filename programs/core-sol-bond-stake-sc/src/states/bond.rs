@@ -9,8 +9,16 @@ pub struct Bond {
     pub bond_amount: u64,
     pub asset_id: Pubkey,
     pub owner: Pubkey,
-    pub padding: [u8; 64],
+    pub asset_mode: u8,
+    pub slash_reason: u8,
+    pub challenged: u8,
+    pub cooldown_end_timestamp: u64,
+    pub auto_renew: u8,
+    pub counted: u8,
+    pub lock_expired: u8,
+    pub unbond_penalty: u64,
+    pub padding: [u8; 42],
 }
 impl Space for Bond {
-    const INIT_SPACE: usize = 8 + 1 + 1 + 8 + 8 + 8 + 32 + 32 + 64;
+    const INIT_SPACE: usize = 8 + 1 + 1 + 8 + 8 + 8 + 32 + 32 + 1 + 1 + 1 + 8 + 1 + 1 + 1 + 8 + 42;
 }
@@ -9,8 +9,11 @@ pub struct BondConfig {
     pub lock_period: u64,
     pub bond_amount: u64,
     pub withdraw_penalty: u64,
-    pub padding: [u8; 32],
+    pub asset_mode: u8,
+    pub cooldown_period: u64,
+    pub pause_flags: u8,
+    pub padding: [u8; 22],
 }
 impl Space for BondConfig {
-    const INIT_SPACE: usize = 8 + 1 + 1 + 1 + 32 + 8 + 8 + 8 + 32;
+    const INIT_SPACE: usize = 8 + 1 + 1 + 1 + 32 + 8 + 8 + 8 + 1 + 8 + 1 + 22;
 }
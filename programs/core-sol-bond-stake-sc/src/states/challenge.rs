@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Challenge {
+    pub bump: u8,
+    pub bond: Pubkey,
+    pub asset_id: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+    pub challenge_timestamp: u64,
+    pub padding: [u8; 32],
+}
+impl Space for Challenge {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 8 + 32;
+}
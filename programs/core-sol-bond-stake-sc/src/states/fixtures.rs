@@ -0,0 +1,130 @@
+//! Zeroed accounts for unit tests, tests override the fields they exercise with struct update
+//! syntax. Other crates enable them with the `test-fixtures` feature.
+
+use anchor_lang::prelude::*;
+
+use crate::{AddressBondsRewards, Bond, LivelinessCurve, RewardsConfig, State, VaultConfig};
+
+pub fn address_bonds_rewards() -> AddressBondsRewards {
+    AddressBondsRewards {
+        bump: 0,
+        address: Pubkey::default(),
+        address_total_bond_amount: 0,
+        current_index: 0,
+        last_update_timestamp: 0,
+        address_rewards_per_share: 0,
+        claimable_amount: 0,
+        vault_bond_id: 0,
+        open_bonds: 0,
+        keeper: Pubkey::default(),
+        liveliness_unbond_sum: 0,
+        liveliness_amount: 0,
+        liveliness_auto_renew_lock_sum: 0,
+        liveliness_lock_sum: 0,
+        synced_index: 0,
+        padding: [0; 12],
+    }
+}
+
+// every field distinct and non zero, a shifted field fails the comparison
+pub fn filled_address_bonds_rewards(address_rewards_per_share: u128) -> AddressBondsRewards {
+    AddressBondsRewards {
+        bump: 254,
+        address: Pubkey::new_unique(),
+        address_total_bond_amount: 1,
+        current_index: 2,
+        last_update_timestamp: 3,
+        address_rewards_per_share,
+        claimable_amount: 4,
+        vault_bond_id: 5,
+        open_bonds: 6,
+        keeper: Pubkey::new_unique(),
+        liveliness_unbond_sum: 7,
+        liveliness_amount: 8,
+        liveliness_auto_renew_lock_sum: 9,
+        liveliness_lock_sum: u128::MAX,
+        synced_index: 10,
+        ..address_bonds_rewards()
+    }
+}
+
+// active and counted in the address liveliness
+pub fn bond() -> Bond {
+    Bond {
+        bump: 0,
+        state: State::Active.to_code(),
+        bond_timestamp: 0,
+        unbond_timestamp: 0,
+        bond_amount: 0,
+        asset_id: Pubkey::default(),
+        owner: Pubkey::default(),
+        asset_mode: 0,
+        slash_reason: 0,
+        challenged: 0,
+        cooldown_end_timestamp: 0,
+        auto_renew: 0,
+        counted: 1,
+        lock_expired: 0,
+        unbond_penalty: 0,
+        padding: [0; 42],
+    }
+}
+
+pub fn rewards_config() -> RewardsConfig {
+    RewardsConfig {
+        bump: 0,
+        rewards_state: State::Active.to_code(),
+        rewards_reserve: 0,
+        accumulated_rewards: 0,
+        rewards_per_slot: 0,
+        rewards_per_share: 0,
+        last_reward_slot: 0,
+        max_apr: 0,
+        liveliness_curve: 0,
+        liveliness_threshold: 0,
+        liveliness_curve_param: 0,
+        padding: [0; 15],
+    }
+}
+
+// every field distinct and non zero, a shifted field fails the comparison
+pub fn filled_rewards_config(rewards_per_share: u128) -> RewardsConfig {
+    RewardsConfig {
+        bump: 254,
+        rewards_reserve: 1,
+        accumulated_rewards: 2,
+        rewards_per_slot: 3,
+        rewards_per_share,
+        last_reward_slot: 4,
+        max_apr: 5,
+        liveliness_curve: LivelinessCurve::QuadraticDecay.to_code(),
+        liveliness_threshold: 6,
+        liveliness_curve_param: 7,
+        ..rewards_config()
+    }
+}
+
+pub fn liveliness_rewards_config(
+    curve: LivelinessCurve,
+    threshold: u64,
+    param: u64,
+) -> RewardsConfig {
+    RewardsConfig {
+        liveliness_curve: curve.to_code(),
+        liveliness_threshold: threshold,
+        liveliness_curve_param: param,
+        ..rewards_config()
+    }
+}
+
+pub fn vault_config() -> VaultConfig {
+    VaultConfig {
+        bump: 0,
+        vault: Pubkey::default(),
+        mint_of_token: Pubkey::default(),
+        total_bond_amount: 0,
+        total_penalized_amount: 0,
+        total_challenge_amount: 0,
+        padding: [0; 24],
+    }
+}
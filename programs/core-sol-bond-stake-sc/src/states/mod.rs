@@ -10,3 +10,9 @@ pub mod asset_usage;
 pub use asset_usage::*;
 pub mod address_bonds_rewards;
 pub use address_bonds_rewards::*;
+pub mod program_config;
+pub use program_config::*;
+pub mod challenge;
+pub use challenge::*;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ProgramConfig {
+    pub bump: u8,
+    pub slashing_authority: Pubkey,
+    pub slash_share: u64,
+    pub slash_destination: u8,
+    pub arbiter: Pubkey,
+    pub challenge_amount: u64,
+    pub emergency_state: u8,
+    pub pause_flags: u8,
+    pub challenge_period: u64,
+    pub padding: [u8; 14],
+}
+impl Space for ProgramConfig {
+    const INIT_SPACE: usize = 8 + 1 + 32 + 8 + 1 + 32 + 8 + 1 + 1 + 8 + 14;
+}
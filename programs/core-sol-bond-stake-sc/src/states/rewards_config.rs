@@ -7,12 +7,19 @@ pub struct RewardsConfig {
     pub rewards_reserve: u64,
     pub accumulated_rewards: u64,
     pub rewards_per_slot: u64,
-    pub rewards_per_share: u64,
+    pub rewards_per_share: u128,
     pub last_reward_slot: u64,
     pub max_apr: u64,
-    pub padding: [u8; 32],
+    pub liveliness_curve: u8,
+    pub liveliness_threshold: u64,
+    pub liveliness_curve_param: u64,
+    pub padding: [u8; 15],
 }
 
 impl Space for RewardsConfig {
-    const INIT_SPACE: usize = 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32;
+    const INIT_SPACE: usize = 8 + 1 + 1 + 8 + 8 + 8 + 16 + 8 + 8 + 1 + 8 + 8 + 15;
+}
+
+impl RewardsConfig {
+    pub const REWARDS_PER_SHARE_OFFSET: usize = 8 + 1 + 1 + 8 + 8 + 8;
 }
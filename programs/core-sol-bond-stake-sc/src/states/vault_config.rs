@@ -7,8 +7,9 @@ pub struct VaultConfig {
     pub mint_of_token: Pubkey,
     pub total_bond_amount: u64,
     pub total_penalized_amount: u64,
-    pub padding: [u8; 32],
+    pub total_challenge_amount: u64,
+    pub padding: [u8; 24],
 }
 impl Space for VaultConfig {
-    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 32;
+    const INIT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 24;
 }
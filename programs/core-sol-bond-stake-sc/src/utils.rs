@@ -1,48 +1,247 @@
 use anchor_lang::prelude::*;
 use solana_program::clock;
 
-use crate::DIVISION_SAFETY_CONST;
+use crate::{
+    BondConfig, Errors, ProgramConfig, DIVISION_SAFETY_CONST, REWARDS_PER_SHARE_PRECISION,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum State {
     Inactive = 0,
     Active = 1,
+    Unbonding = 2,
 }
 impl State {
     pub fn to_code(&self) -> u8 {
         match self {
             State::Inactive => 0,
             State::Active => 1,
+            State::Unbonding => 2,
         }
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum AssetMode {
+    // any leaf delegate is accepted
+    Unrestricted = 0,
+    // leaf delegate must already be the asset authority PDA, only recorded since bubblegum
+    // still lets the owner transfer or burn the leaf, use Escrow to lock it
+    DelegateRequired = 1,
+    // bond delegates the leaf to the asset authority PDA and withdraw revokes it, only
+    // recorded like DelegateRequired
+    Delegate = 2,
+    // bond transfers the leaf to the asset authority PDA, withdraw returns it
+    Escrow = 3,
+}
+impl AssetMode {
+    pub fn to_code(&self) -> u8 {
+        match self {
+            AssetMode::Unrestricted => 0,
+            AssetMode::DelegateRequired => 1,
+            AssetMode::Delegate => 2,
+            AssetMode::Escrow => 3,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum SlashDestination {
+    // slashed amount is added to the penalized amount
+    Treasury = 0,
+    // slashed amount is added to the rewards reserve
+    RewardsReserve = 1,
+}
+impl SlashDestination {
+    pub fn to_code(&self) -> u8 {
+        match self {
+            SlashDestination::Treasury => 0,
+            SlashDestination::RewardsReserve => 1,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PauseFlag {
+    // bond and bond_many
+    Bond = 1,
+    // renew, auto renew toggles and cancel_unbond
+    Renew = 2,
+    // withdraw, partial_withdraw and the unbonding flow
+    Withdraw = 4,
+    TopUp = 8,
+    StakeRewards = 16,
+    UpdateVaultBond = 32,
+}
+impl PauseFlag {
+    pub fn to_code(&self) -> u8 {
+        match self {
+            PauseFlag::Bond => 1,
+            PauseFlag::Renew => 2,
+            PauseFlag::Withdraw => 4,
+            PauseFlag::TopUp => 8,
+            PauseFlag::StakeRewards => 16,
+            PauseFlag::UpdateVaultBond => 32,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum LivelinessCurve {
+    // payout share equals the liveliness score
+    Linear = 0,
+    // fixed payout share (curve param) below the threshold
+    Step = 1,
+    // payout share decays with the square of the distance to the threshold
+    QuadraticDecay = 2,
+}
+impl LivelinessCurve {
+    pub fn to_code(&self) -> u8 {
+        match self {
+            LivelinessCurve::Linear => 0,
+            LivelinessCurve::Step => 1,
+            LivelinessCurve::QuadraticDecay => 2,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeafArgs {
+    pub nonce: u64,
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+}
+
+// tier flags and global flags both pause an operation
+pub fn is_paused(
+    bond_config: &BondConfig,
+    program_config: &ProgramConfig,
+    flag: PauseFlag,
+) -> bool {
+    (bond_config.pause_flags | program_config.pause_flags) & flag.to_code() != 0
+}
+
+// bond id 0 targets the vault bond
+pub fn get_target_bond_id(bond_id: u16, vault_bond_id: u16) -> u16 {
+    if bond_id == 0 {
+        vault_bond_id
+    } else {
+        bond_id
+    }
+}
+
 pub fn get_current_timestamp() -> Result<u64> {
-    Ok(clock::Clock::get()?.unix_timestamp.try_into().unwrap())
+    let timestamp = clock::Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .map_err(|_| Errors::MathUnderflow)?;
+
+    Ok(timestamp)
 }
 
 pub fn get_current_slot() -> Result<u64> {
-    Ok(clock::Clock::get()?.slot.try_into().unwrap())
+    Ok(clock::Clock::get()?.slot)
 }
 
-pub fn compute_bond_score(lock_period: u64, current_timestamp: u64, unbond_timestamp: u64) -> u64 {
-    if current_timestamp >= unbond_timestamp {
-        0
+// auto renewing bonds always have a fresh lock period ahead of them
+pub fn get_unbond_timestamp(
+    auto_renew: u8,
+    lock_period: u64,
+    current_timestamp: u64,
+    unbond_timestamp: u64,
+) -> Result<u64> {
+    if auto_renew == 1 {
+        current_timestamp
+            .checked_add(lock_period)
+            .ok_or(Errors::MathOverflow.into())
     } else {
-        let difference = unbond_timestamp - current_timestamp;
+        Ok(unbond_timestamp)
+    }
+}
+
+// rewrites a u64 rewards per share of the legacy scale at offset as a u128 of the current
+// one, the fields after it move up by 8 bytes into the zero filled tail of the reallocation
+pub fn widen_rewards_per_share(data: &mut [u8], offset: usize) {
+    let mut legacy = [0u8; 8];
+    legacy.copy_from_slice(&data[offset..offset + 8]);
+
+    let len = data.len();
+    data.copy_within(offset + 8..len - 8, offset + 16);
+
+    let rewards_per_share = u64::from_le_bytes(legacy) as u128
+        * (REWARDS_PER_SHARE_PRECISION / DIVISION_SAFETY_CONST as u128);
+
+    data[offset..offset + 16].copy_from_slice(&rewards_per_share.to_le_bytes());
+}
+
+#[cfg(test)]
+mod unbond_timestamp_tests {
+    use super::*;
+
+    #[test]
+    fn auto_renew_unbond_timestamp_overflow() {
+        assert_eq!(get_unbond_timestamp(0, u64::MAX, 1, 2), Ok(2));
+        assert_eq!(get_unbond_timestamp(1, u64::MAX - 1, 1, 2), Ok(u64::MAX));
+        assert_eq!(
+            get_unbond_timestamp(1, u64::MAX, 1, 2),
+            Err(Errors::MathOverflow.into())
+        );
+    }
+}
+
+#[cfg(test)]
+mod widen_rewards_per_share_tests {
+    use super::*;
+    use crate::{
+        states::fixtures::{filled_address_bonds_rewards, filled_rewards_config},
+        AddressBondsRewards, RewardsConfig,
+    };
+
+    // serializes the account, then swaps the u128 for its u64 legacy layout padded to the new size
+    fn legacy_layout<T: AccountSerialize>(account: &T, offset: usize, legacy: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+
+        data.splice(offset..offset + 16, legacy.to_le_bytes());
+        data.resize(data.len() + 8, 0);
+
+        data
+    }
+
+    #[test]
+    fn rewards_config_is_widened() {
+        let scale = (REWARDS_PER_SHARE_PRECISION / DIVISION_SAFETY_CONST as u128) as u64;
+
+        for legacy in [0, 1, DIVISION_SAFETY_CONST, u64::MAX] {
+            let account = filled_rewards_config(legacy as u128 * scale as u128);
+            let mut data = legacy_layout(&account, RewardsConfig::REWARDS_PER_SHARE_OFFSET, legacy);
+
+            widen_rewards_per_share(&mut data, RewardsConfig::REWARDS_PER_SHARE_OFFSET);
+
+            let mut expected = Vec::new();
+            account.try_serialize(&mut expected).unwrap();
+            assert_eq!(data, expected);
+        }
+    }
+
+    #[test]
+    fn address_bonds_rewards_is_widened() {
+        let scale = (REWARDS_PER_SHARE_PRECISION / DIVISION_SAFETY_CONST as u128) as u64;
 
-        if lock_period == 0 {
-            0
-        } else {
-            let div_result = 10_000_000_000_000u64.checked_div(lock_period).unwrap_or(0);
+        for legacy in [0, 1, DIVISION_SAFETY_CONST, u64::MAX] {
+            let account = filled_address_bonds_rewards(legacy as u128 * scale as u128);
+            let mut data = legacy_layout(
+                &account,
+                AddressBondsRewards::REWARDS_PER_SHARE_OFFSET,
+                legacy,
+            );
 
-            let liveliness = div_result
-                .checked_mul(difference)
-                .unwrap_or(0)
-                .checked_div(DIVISION_SAFETY_CONST)
-                .unwrap_or(0);
+            widen_rewards_per_share(&mut data, AddressBondsRewards::REWARDS_PER_SHARE_OFFSET);
 
-            liveliness
+            let mut expected = Vec::new();
+            account.try_serialize(&mut expected).unwrap();
+            assert_eq!(data, expected);
         }
     }
 }
@@ -116,6 +116,16 @@ describe("core-sol-bond-stake-sc", () => {
     program.programId
   )[0];
 
+  const programConfigPda = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    program.programId
+  )[0];
+
+  const assetAuthorityPda = PublicKey.findProgramAddressSync(
+    [Buffer.from("asset_authority")],
+    program.programId
+  )[0];
+
   const vault_ata = getAssociatedTokenAddressSync(
     itheum_token_mint.publicKey,
     vaultConfigPda,
@@ -147,6 +157,16 @@ describe("core-sol-bond-stake-sc", () => {
     return signature;
   };
 
+  // asset usage of the asset held by a bond, renewals are checked against its bond config
+  const assetUsageOf = async (bond: PublicKey): Promise<PublicKey> => {
+    const bondAcc = await program.account.bond.fetch(bond);
+
+    return PublicKey.findProgramAddressSync(
+      [bondAcc.assetId.toBuffer()],
+      program.programId
+    )[0];
+  };
+
   before("Airdrop and create mints and collections", async () => {
     let lamports = await getMinimumBalanceForRentExemptMint(connection);
 
@@ -400,6 +420,46 @@ describe("core-sol-bond-stake-sc", () => {
     assert(rewards_config.maxApr.eq(new anchor.BN(0)));
   });
 
+  it("Initialize program config by user (should fail)", async () => {
+    try {
+      await program.methods
+        .initializeProgramConfig(user.publicKey, new anchor.BN(5000))
+        .signers([user])
+        .accounts({
+          programConfig: programConfigPda,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2012);
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "An address constraint was violated"
+      );
+    }
+  });
+
+  it("Initialize program config by admin", async () => {
+    await program.methods
+      .initializeProgramConfig(admin.publicKey, new anchor.BN(5000))
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let programConfigAcc = await program.account.programConfig.fetch(
+      programConfigPda
+    );
+
+    expect(programConfigAcc.slashingAuthority.toBase58()).to.equal(
+      admin.publicKey.toBase58()
+    );
+    expect(programConfigAcc.slashShare.toNumber()).to.equal(5000);
+    expect(programConfigAcc.slashDestination).to.equal(0);
+  });
+
   it("Create bond Config by user (should fail)", async () => {
     const bondConfigPda2 = PublicKey.findProgramAddressSync(
       [Buffer.from("bond_config"), Buffer.from([2])],
@@ -897,6 +957,71 @@ describe("core-sol-bond-stake-sc", () => {
     assert(bond_config2.withdrawPenalty.eq(new anchor.BN(5000)));
   });
 
+  it("Update asset mode by user (should fail)", async () => {
+    try {
+      await program.methods
+        .updateAssetMode(1, 1)
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2012);
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "An address constraint was violated"
+      );
+    }
+  });
+
+  it("Update asset mode by admin - wrong value (should fail)", async () => {
+    try {
+      await program.methods
+        .updateAssetMode(1, 9)
+        .signers([admin])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          authority: admin.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Wrong value"
+      );
+    }
+  });
+
+  it("Update asset mode by admin", async () => {
+    await program.methods
+      .updateAssetMode(1, 1)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bond_config = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    expect(bond_config.assetMode).to.equal(1);
+
+    await program.methods
+      .updateAssetMode(1, 0)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    bond_config = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    expect(bond_config.assetMode).to.equal(0);
+  });
+
   // Rewards Config
 
   it("Set rewards state by user (should fail)", async () => {
@@ -1287,6 +1412,7 @@ describe("core-sol-bond-stake-sc", () => {
           assetUsage: assetUsage1,
           bond: bond1,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           vault: vault_ata,
@@ -1298,6 +1424,11 @@ describe("core-sol-bond-stake-sc", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          leafDelegate: user.publicKey,
+          assetAuthority: assetAuthorityPda,
+          treeConfig: null,
+          bubblegumProgram: null,
+          logWrapper: null,
         })
         .remainingAccounts([
           {
@@ -1371,6 +1502,7 @@ describe("core-sol-bond-stake-sc", () => {
           assetUsage: assetUsage1,
           bond: bond1,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           vault: vault_ata,
@@ -1382,6 +1514,11 @@ describe("core-sol-bond-stake-sc", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          leafDelegate: user.publicKey,
+          assetAuthority: assetAuthorityPda,
+          treeConfig: null,
+          bubblegumProgram: null,
+          logWrapper: null,
         })
         .remainingAccounts([
           {
@@ -1437,6 +1574,7 @@ describe("core-sol-bond-stake-sc", () => {
           assetUsage: assetUsage1,
           bond: bond1,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           vault: vault_ata,
@@ -1448,6 +1586,11 @@ describe("core-sol-bond-stake-sc", () => {
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          leafDelegate: user.publicKey,
+          assetAuthority: assetAuthorityPda,
+          treeConfig: null,
+          bubblegumProgram: null,
+          logWrapper: null,
         })
         .remainingAccounts([
           {
@@ -1502,6 +1645,7 @@ describe("core-sol-bond-stake-sc", () => {
         assetUsage: assetUsage1,
         bond: bond1,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         vault: vault_ata,
@@ -1513,6 +1657,11 @@ describe("core-sol-bond-stake-sc", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        leafDelegate: user.publicKey,
+        assetAuthority: assetAuthorityPda,
+        treeConfig: null,
+        bubblegumProgram: null,
+        logWrapper: null,
       })
       .remainingAccounts([
         {
@@ -1592,6 +1741,7 @@ describe("core-sol-bond-stake-sc", () => {
         assetUsage: assetUsage2,
         bond: bond2,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         vault: vault_ata,
@@ -1603,6 +1753,11 @@ describe("core-sol-bond-stake-sc", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        leafDelegate: user.publicKey,
+        assetAuthority: assetAuthorityPda,
+        treeConfig: null,
+        bubblegumProgram: null,
+        logWrapper: null,
       })
       .remainingAccounts([
         {
@@ -1692,6 +1847,7 @@ describe("core-sol-bond-stake-sc", () => {
         assetUsage: assetUsage1,
         bond: bond1,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         vault: vault_ata,
@@ -1703,6 +1859,11 @@ describe("core-sol-bond-stake-sc", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        leafDelegate: user2.publicKey,
+        assetAuthority: assetAuthorityPda,
+        treeConfig: null,
+        bubblegumProgram: null,
+        logWrapper: null,
       })
       .remainingAccounts([
         {
@@ -1768,10 +1929,13 @@ describe("core-sol-bond-stake-sc", () => {
         .signers([user])
         .accounts({
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           addressBondsRewards: userBondsRewards,
           bond: bond1,
+          assetUsage: await assetUsageOf(bond1),
+          owner: user.publicKey,
           authority: user.publicKey,
         })
         .rpc();
@@ -1804,10 +1968,13 @@ describe("core-sol-bond-stake-sc", () => {
       .signers([user])
       .accounts({
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         addressBondsRewards: userBondsRewards,
         bond: bond1,
+        assetUsage: await assetUsageOf(bond1),
+        owner: user.publicKey,
         authority: user.publicKey,
       })
       .rpc();
@@ -1833,7 +2000,7 @@ describe("core-sol-bond-stake-sc", () => {
     expect(bondAcc.bondAmount.toNumber() / LAMPORTS_PER_SOL).to.equal(100);
   });
 
-  it("TopUp bond 2 by user - bond not vault (should fail)", async () => {
+  it("TopUp vault bond by user - vault not set (should fail)", async () => {
     const userBondsRewards = PublicKey.findProgramAddressSync(
       [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
       program.programId
@@ -1850,11 +2017,12 @@ describe("core-sol-bond-stake-sc", () => {
 
     try {
       let x = await program.methods
-        .topUp(1, 2, new anchor.BN(100e9))
+        .topUp(1, 0, new anchor.BN(100e9))
         .signers([user])
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: itheum_token_mint.publicKey,
           bond: bond2,
@@ -1866,9 +2034,9 @@ describe("core-sol-bond-stake-sc", () => {
         .rpc();
       assert(false, "Should have thrown error");
     } catch (err) {
-      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(6019);
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2006);
       expect((err as anchor.AnchorError).error.errorMessage).to.equal(
-        "Vault bond id mismatch"
+        "A seeds constraint was violated"
       );
     }
   });
@@ -1894,6 +2062,7 @@ describe("core-sol-bond-stake-sc", () => {
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: another_token_mint.publicKey,
           bond: bond1,
@@ -1934,6 +2103,7 @@ describe("core-sol-bond-stake-sc", () => {
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: itheum_token_mint.publicKey,
           bond: bond1,
@@ -1952,7 +2122,7 @@ describe("core-sol-bond-stake-sc", () => {
     }
   });
 
-  it("TopUp bond 1 by user - vault not set (should fail)", async () => {
+  it("TopUp vault bond by user - bond 1 passed, vault not set (should fail)", async () => {
     const userBondsRewards = PublicKey.findProgramAddressSync(
       [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
       program.programId
@@ -1969,11 +2139,12 @@ describe("core-sol-bond-stake-sc", () => {
 
     try {
       let x = await program.methods
-        .topUp(1, 1, new anchor.BN(100e9))
+        .topUp(1, 0, new anchor.BN(100e9))
         .signers([user])
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: itheum_token_mint.publicKey,
           bond: bond1,
@@ -1985,9 +2156,9 @@ describe("core-sol-bond-stake-sc", () => {
         .rpc();
       assert(false, "Should have thrown error");
     } catch (err) {
-      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(6019);
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2006);
       expect((err as anchor.AnchorError).error.errorMessage).to.equal(
-        "Vault bond id mismatch"
+        "A seeds constraint was violated"
       );
     }
   });
@@ -2018,6 +2189,8 @@ describe("core-sol-bond-stake-sc", () => {
         addressBondsRewards: userBondsRewards,
         bond: bond1,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        owner: user.publicKey,
         authority: user.publicKey,
       })
       .rpc();
@@ -2050,6 +2223,7 @@ describe("core-sol-bond-stake-sc", () => {
       .accounts({
         addressBondsRewards: userBondsRewards,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         mintOfTokenSent: itheum_token_mint.publicKey,
         bond: bond1,
@@ -2099,6 +2273,8 @@ describe("core-sol-bond-stake-sc", () => {
         addressBondsRewards: userBondsRewards,
         bond: bond2,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        owner: user.publicKey,
         authority: user.publicKey,
       })
       .rpc();
@@ -2110,7 +2286,7 @@ describe("core-sol-bond-stake-sc", () => {
     expect(userAcc.vaultBondId).to.equal(2);
   });
 
-  it("TopUp bond 1 by user - vault set to other bond (should fail)", async () => {
+  it("TopUp vault bond by user - vault set to other bond (should fail)", async () => {
     const userBondsRewards = PublicKey.findProgramAddressSync(
       [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
       program.programId
@@ -2127,11 +2303,12 @@ describe("core-sol-bond-stake-sc", () => {
 
     try {
       let x = await program.methods
-        .topUp(1, 1, new anchor.BN(100e9))
+        .topUp(1, 0, new anchor.BN(100e9))
         .signers([user])
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: itheum_token_mint.publicKey,
           bond: bond1,
@@ -2143,9 +2320,9 @@ describe("core-sol-bond-stake-sc", () => {
         .rpc();
       assert(false, "Should have thrown error");
     } catch (err) {
-      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(6019);
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2006);
       expect((err as anchor.AnchorError).error.errorMessage).to.equal(
-        "Vault bond id mismatch"
+        "A seeds constraint was violated"
       );
     }
   });
@@ -2172,7 +2349,7 @@ describe("core-sol-bond-stake-sc", () => {
     ).value.amount;
 
     let x = await program.methods
-      .withdraw(1, 1)
+      .withdraw(1, 1, null)
       .signers([user])
       .accounts({
         addressBondsRewards: userBondsRewards,
@@ -2184,6 +2361,13 @@ describe("core-sol-bond-stake-sc", () => {
         vault: vault_ata,
         authority: user.publicKey,
         authorityTokenAccount: itheum_token_user_ata,
+        merkleTree: null,
+        treeConfig: null,
+        assetAuthority: null,
+        bubblegumProgram: null,
+        logWrapper: null,
+        compressionProgram: null,
+        programConfig: programConfigPda,
       })
       .rpc();
 
@@ -2232,7 +2416,7 @@ describe("core-sol-bond-stake-sc", () => {
 
     try {
       await program.methods
-        .withdraw(1, 1)
+        .withdraw(1, 1, null)
         .signers([user])
         .accounts({
           addressBondsRewards: userBondsRewards,
@@ -2244,6 +2428,13 @@ describe("core-sol-bond-stake-sc", () => {
           vault: vault_ata,
           authority: user.publicKey,
           authorityTokenAccount: itheum_token_user_ata,
+          merkleTree: null,
+          treeConfig: null,
+          assetAuthority: null,
+          bubblegumProgram: null,
+          logWrapper: null,
+          compressionProgram: null,
+          programConfig: programConfigPda,
         })
         .rpc();
       assert(false, "Should have thrown error");
@@ -2277,6 +2468,7 @@ describe("core-sol-bond-stake-sc", () => {
         .accounts({
           addressBondsRewards: userBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           mintOfTokenSent: itheum_token_mint.publicKey,
           bond: bond1,
@@ -2315,10 +2507,13 @@ describe("core-sol-bond-stake-sc", () => {
         .signers([user])
         .accounts({
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           addressBondsRewards: userBondsRewards,
           bond: bond1,
+          assetUsage: await assetUsageOf(bond1),
+          owner: user.publicKey,
           authority: user.publicKey,
         })
         .rpc();
@@ -2386,10 +2581,13 @@ describe("core-sol-bond-stake-sc", () => {
       .signers([user])
       .accounts({
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         addressBondsRewards: userBondsRewards,
         bond: bond2,
+        assetUsage: await assetUsageOf(bond2),
+        owner: user.publicKey,
         authority: user.publicKey,
       })
       .rpc();
@@ -2401,8 +2599,8 @@ describe("core-sol-bond-stake-sc", () => {
     expect(userAccAfter.claimableAmount.toNumber()).to.equal(
       userAccBefore.claimableAmount.toNumber() + 5e5
     );
-    expect(userAccAfter.addressRewardsPerShare.toNumber()).to.equal(
-      userAccBefore.addressRewardsPerShare.toNumber() + 5e3
+    expect(userAccAfter.addressRewardsPerShare.toString()).to.equal(
+      userAccBefore.addressRewardsPerShare.add(new anchor.BN(5e12)).toString()
     );
   });
 
@@ -2430,10 +2628,13 @@ describe("core-sol-bond-stake-sc", () => {
       .signers([user2])
       .accounts({
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         addressBondsRewards: userBondsRewards,
         bond: bond1,
+        assetUsage: await assetUsageOf(bond1),
+        owner: user2.publicKey,
         authority: user2.publicKey,
       })
       .rpc();
@@ -2446,8 +2647,8 @@ describe("core-sol-bond-stake-sc", () => {
       userAccBefore.claimableAmount.toNumber() + 10e5
     );
 
-    expect(userAccAfter.addressRewardsPerShare.toNumber()).to.equal(
-      userAccBefore.addressRewardsPerShare.toNumber() + 10e3
+    expect(userAccAfter.addressRewardsPerShare.toString()).to.equal(
+      userAccBefore.addressRewardsPerShare.add(new anchor.BN(10e12)).toString()
     );
   });
 
@@ -2491,6 +2692,7 @@ describe("core-sol-bond-stake-sc", () => {
         assetUsage: assetUsage3,
         bond: bond3,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         vault: vault_ata,
@@ -2502,6 +2704,11 @@ describe("core-sol-bond-stake-sc", () => {
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        leafDelegate: user.publicKey,
+        assetAuthority: assetAuthorityPda,
+        treeConfig: null,
+        bubblegumProgram: null,
+        logWrapper: null,
       })
       .remainingAccounts([
         {
@@ -2520,12 +2727,12 @@ describe("core-sol-bond-stake-sc", () => {
       userAccBefore.claimableAmount.toNumber() + 10e5
     );
 
-    expect(userAccAfter.addressRewardsPerShare.toNumber()).to.equal(
-      userAccBefore.addressRewardsPerShare.toNumber() + 10e3
+    expect(userAccAfter.addressRewardsPerShare.toString()).to.equal(
+      userAccBefore.addressRewardsPerShare.add(new anchor.BN(10e12)).toString()
     );
   });
 
-  it("Stake rewards user2 - no vault set (should fail)", async () => {
+  it("Stake rewards user2 into vault bond - no vault set (should fail)", async () => {
     const addressBondsRewards = PublicKey.findProgramAddressSync(
       [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
       program.programId
@@ -2542,21 +2749,24 @@ describe("core-sol-bond-stake-sc", () => {
 
     try {
       let x = await program.methods
-        .stakeRewards(1, 1)
+        .stakeRewards(1, 0)
         .signers([user2])
         .accounts({
           addressBondsRewards: addressBondsRewards,
           bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
           rewardsConfig: rewardsConfigPda,
           vaultConfig: vaultConfigPda,
           bond: bond,
+          assetUsage: await assetUsageOf(bond),
+          owner: user2.publicKey,
           authority: user2.publicKey,
         })
         .rpc();
     } catch (e) {
-      expect((e as anchor.AnchorError).error.errorCode.number).to.equal(6019);
+      expect((e as anchor.AnchorError).error.errorCode.number).to.equal(2006);
       expect((e as anchor.AnchorError).error.errorMessage).to.equal(
-        "Vault bond id mismatch"
+        "A seeds constraint was violated"
       );
     }
   });
@@ -2587,6 +2797,8 @@ describe("core-sol-bond-stake-sc", () => {
         addressBondsRewards: addressBondsRewards,
         bond: bond,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        owner: user2.publicKey,
         authority: user2.publicKey,
       })
       .rpc();
@@ -2615,9 +2827,12 @@ describe("core-sol-bond-stake-sc", () => {
       .accounts({
         addressBondsRewards: addressBondsRewards,
         bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
         rewardsConfig: rewardsConfigPda,
         vaultConfig: vaultConfigPda,
         bond: bond,
+        assetUsage: await assetUsageOf(bond),
+        owner: user2.publicKey,
         authority: user2.publicKey,
       })
       .rpc();
@@ -2635,8 +2850,8 @@ describe("core-sol-bond-stake-sc", () => {
     );
 
     expect(userAcc.claimableAmount.toNumber()).to.equal(0);
-    expect(userAcc.addressRewardsPerShare.toNumber()).to.equal(
-      rewardsConfigAcc.rewardsPerShare.toNumber()
+    expect(userAcc.addressRewardsPerShare.toString()).to.equal(
+      rewardsConfigAcc.rewardsPerShare.toString()
     );
   });
 
@@ -2691,8 +2906,8 @@ describe("core-sol-bond-stake-sc", () => {
       rewardsConfigPda
     );
 
-    expect(userAccAfter.addressRewardsPerShare.toNumber()).to.equal(
-      rewardsConfigAcc.rewardsPerShare.toNumber()
+    expect(userAccAfter.addressRewardsPerShare.toString()).to.equal(
+      rewardsConfigAcc.rewardsPerShare.toString()
     );
     expect(userAccAfter.claimableAmount.toNumber()).to.equal(0);
     expect(userAccAfter.claimableAmount.toNumber()).to.equal(0);
@@ -2723,7 +2938,7 @@ describe("core-sol-bond-stake-sc", () => {
     ).value.amount;
 
     let x = await program.methods
-      .withdraw(1, 2)
+      .withdraw(1, 2, null)
       .signers([user])
       .accounts({
         addressBondsRewards: userBondsRewards,
@@ -2735,6 +2950,13 @@ describe("core-sol-bond-stake-sc", () => {
         vault: vault_ata,
         authority: user.publicKey,
         authorityTokenAccount: itheum_token_user_ata,
+        merkleTree: null,
+        treeConfig: null,
+        assetAuthority: null,
+        bubblegumProgram: null,
+        logWrapper: null,
+        compressionProgram: null,
+        programConfig: programConfigPda,
       })
       .rpc();
 
@@ -2773,7 +2995,7 @@ describe("core-sol-bond-stake-sc", () => {
     ).value.amount;
 
     let x = await program.methods
-      .withdraw(1, 3)
+      .withdraw(1, 3, null)
       .signers([user])
       .accounts({
         addressBondsRewards: userBondsRewards,
@@ -2785,6 +3007,13 @@ describe("core-sol-bond-stake-sc", () => {
         vault: vault_ata,
         authority: user.publicKey,
         authorityTokenAccount: itheum_token_user_ata,
+        merkleTree: null,
+        treeConfig: null,
+        assetAuthority: null,
+        bubblegumProgram: null,
+        logWrapper: null,
+        compressionProgram: null,
+        programConfig: programConfigPda,
       })
       .rpc();
 
@@ -2800,4 +3029,1829 @@ describe("core-sol-bond-stake-sc", () => {
     expect(bondAcc.bondAmount.toNumber()).to.equal(0);
     expect(Number(userBalanceAfter)).to.equal(Number(800003499800));
   });
+
+  // Slashing
+
+  it("Open challenge on bond 1 of user2 - challenges disabled (should fail)", async () => {
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const challenge = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), bond.toBuffer()],
+      program.programId
+    )[0];
+
+    const assetUsage = PublicKey.findProgramAddressSync(
+      [toWeb3JsPublicKey(user2_nft_leaf_schemas[0].id).toBuffer()],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .openChallenge(1)
+        .signers([user])
+        .accounts({
+          programConfig: programConfigPda,
+          challenge: challenge,
+          bond: bond,
+          assetUsage: assetUsage,
+          owner: user2.publicKey,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          mintOfTokenSent: itheum_token_mint.publicKey,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Challenges are disabled"
+      );
+    }
+  });
+
+  it("Update challenge amount by admin", async () => {
+    await program.methods
+      .updateChallengeAmount(new anchor.BN(10e9))
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let programConfigAcc = await program.account.programConfig.fetch(
+      programConfigPda
+    );
+
+    expect(programConfigAcc.challengeAmount.toNumber()).to.equal(10e9);
+    expect(programConfigAcc.arbiter.toBase58()).to.equal(
+      admin.publicKey.toBase58()
+    );
+  });
+
+  it("Open challenge on bond 1 of user2 by user", async () => {
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const challenge = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), bond.toBuffer()],
+      program.programId
+    )[0];
+
+    const assetUsage = PublicKey.findProgramAddressSync(
+      [toWeb3JsPublicKey(user2_nft_leaf_schemas[0].id).toBuffer()],
+      program.programId
+    )[0];
+
+    await program.methods
+      .openChallenge(1)
+      .signers([user])
+      .accounts({
+        programConfig: programConfigPda,
+        challenge: challenge,
+        bond: bond,
+        assetUsage: assetUsage,
+        owner: user2.publicKey,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        mintOfTokenSent: itheum_token_mint.publicKey,
+        authority: user.publicKey,
+        authorityTokenAccount: itheum_token_user_ata,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond);
+    let challengeAcc = await program.account.challenge.fetch(challenge);
+
+    expect(bondAcc.challenged).to.equal(1);
+    expect(challengeAcc.bond.toBase58()).to.equal(bond.toBase58());
+    expect(challengeAcc.challenger.toBase58()).to.equal(
+      user.publicKey.toBase58()
+    );
+    expect(challengeAcc.amount.toNumber()).to.equal(10e9);
+  });
+
+  it("Withdraw bond 1 by user2 - bond challenged (should fail)", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .withdraw(1, 1, null)
+        .signers([user2])
+        .accounts({
+          addressBondsRewards: addressBondsRewards,
+          bondConfig: bondConfigPda1,
+          rewardsConfig: rewardsConfigPda,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          bond: bond,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          authority: user2.publicKey,
+          authorityTokenAccount: itheum_token_user2_ata,
+          merkleTree: null,
+          treeConfig: null,
+          assetAuthority: null,
+          bubblegumProgram: null,
+          logWrapper: null,
+          compressionProgram: null,
+          programConfig: programConfigPda,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Bond is challenged"
+      );
+    }
+  });
+
+  it("Resolve challenge on bond 1 of user2 by user (should fail)", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const challenge = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), bond.toBuffer()],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .resolveChallenge(1, true, 1)
+        .signers([user])
+        .accounts({
+          programConfig: programConfigPda,
+          challenge: challenge,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          addressBondsRewards: addressBondsRewards,
+          bond: bond,
+          owner: user2.publicKey,
+          challenger: user.publicKey,
+          challengerTokenAccount: itheum_token_user_ata,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Not privileged"
+      );
+    }
+  });
+
+  it("Resolve challenge on bond 1 of user2 by arbiter - rejected", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const challenge = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), bond.toBuffer()],
+      program.programId
+    )[0];
+
+    let vaultAccBefore = await program.account.vaultConfig.fetch(
+      vaultConfigPda
+    );
+
+    await program.methods
+      .resolveChallenge(1, false, 0)
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        challenge: challenge,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        mintOfTokenToReceive: itheum_token_mint.publicKey,
+        addressBondsRewards: addressBondsRewards,
+        bond: bond,
+        owner: user2.publicKey,
+        challenger: user.publicKey,
+        challengerTokenAccount: itheum_token_user_ata,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond);
+    let vaultAccAfter = await program.account.vaultConfig.fetch(
+      vaultConfigPda
+    );
+
+    expect(bondAcc.challenged).to.equal(0);
+    expect(bondAcc.slashReason).to.equal(0);
+    expect(vaultAccAfter.totalPenalizedAmount.toString()).to.equal(
+      vaultAccBefore.totalPenalizedAmount.add(new anchor.BN(10e9)).toString()
+    );
+    expect(await connection.getAccountInfo(challenge)).to.be.null;
+  });
+
+  it("Slash bond 1 of user2 by user (should fail)", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .slashBond(1, 1)
+        .signers([user])
+        .accounts({
+          programConfig: programConfigPda,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          addressBondsRewards: addressBondsRewards,
+          bond: bond,
+          owner: user2.publicKey,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Not privileged"
+      );
+    }
+  });
+
+  it("Slash bond 1 of user2 by slashing authority", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    let bondAccBefore = await program.account.bond.fetch(bond);
+    let vaultAccBefore = await program.account.vaultConfig.fetch(
+      vaultConfigPda
+    );
+
+    await program.methods
+      .slashBond(1, 1)
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: addressBondsRewards,
+        bond: bond,
+        owner: user2.publicKey,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bondAccAfter = await program.account.bond.fetch(bond);
+    let vaultAccAfter = await program.account.vaultConfig.fetch(
+      vaultConfigPda
+    );
+
+    const slashed = bondAccBefore.bondAmount.divn(2);
+
+    expect(bondAccAfter.slashReason).to.equal(1);
+    expect(bondAccAfter.bondAmount.toString()).to.equal(
+      bondAccBefore.bondAmount.sub(slashed).toString()
+    );
+    expect(vaultAccAfter.totalBondAmount.toString()).to.equal(
+      vaultAccBefore.totalBondAmount.sub(slashed).toString()
+    );
+    expect(vaultAccAfter.totalPenalizedAmount.toString()).to.equal(
+      vaultAccBefore.totalPenalizedAmount.add(slashed).toString()
+    );
+  });
+
+  it("Slash bond 1 of user2 - already slashed (should fail)", async () => {
+    const addressBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user2.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user2.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .slashBond(1, 2)
+        .signers([admin])
+        .accounts({
+          programConfig: programConfigPda,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          addressBondsRewards: addressBondsRewards,
+          bond: bond,
+          owner: user2.publicKey,
+          authority: admin.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Bond is slashed"
+      );
+    }
+  });
+
+  it("Bond many - bonds 4 and 5 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const assets = [3, 4].map((i) => user_nft_leaf_schemas[i]);
+
+    const bonds = [4, 5].map((id) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("bond"),
+          user.publicKey.toBuffer(),
+          new anchor.BN(id).toBuffer("le", 2),
+        ],
+        program.programId
+      )
+    );
+
+    const assetUsages = assets.map((asset) =>
+      PublicKey.findProgramAddressSync(
+        [toWeb3JsPublicKey(asset.id).toBuffer()],
+        program.programId
+      )
+    );
+
+    await program.methods
+      .bondMany(
+        1,
+        Array.from(bs58.decode(assets[0].id)),
+        assets.map((asset, i) => ({
+          nonce: new anchor.BN(Number(asset.nonce)),
+          dataHash: Array.from(asset.dataHash),
+          creatorHash: Array.from(asset.creatorHash),
+          leafDelegate: user.publicKey,
+          proofLen: 1,
+          bondBump: bonds[i][1],
+          assetUsageBump: assetUsages[i][1],
+        }))
+      )
+      .signers([user])
+      .accounts({
+        addressBondsRewards: userBondsRewards,
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        mintOfTokenSent: itheum_token_mint.publicKey,
+        authority: user.publicKey,
+        merkleTree: merkleTree,
+        assetAuthority: assetAuthorityPda,
+        authorityTokenAccount: itheum_token_user_ata,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [0, 1].flatMap((i) => [
+          { pubkey: bonds[i][0], isSigner: false, isWritable: true },
+          { pubkey: assetUsages[i][0], isSigner: false, isWritable: true },
+          { pubkey: merkleTree, isSigner: false, isWritable: false },
+        ])
+      )
+      .rpc();
+
+    let addressBondsRewards = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    expect(addressBondsRewards.currentIndex).to.equal(5);
+
+    for (const [bond] of bonds) {
+      let bondAcc = await program.account.bond.fetch(bond);
+      expect(bondAcc.state).to.equal(1);
+      expect(bondAcc.bondAmount.toNumber()).to.equal(100e9);
+      expect(bondAcc.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    }
+  });
+
+  it("Partial withdraw bond 4 by user - bond locked (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .partialWithdraw(1, 4, new anchor.BN(10e9))
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
+          addressBondsRewards: userBondsRewards,
+          rewardsConfig: rewardsConfigPda,
+          bond: bond4,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Bond is locked"
+      );
+    }
+  });
+
+  it("Update cooldown period by admin", async () => {
+    await program.methods
+      .updateCooldownPeriod(1, new anchor.BN(1000))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bond_config = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    assert(bond_config.cooldownPeriod.eq(new anchor.BN(1000)));
+  });
+
+  it("Withdraw bond 5 by user - unbonding required (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .withdraw(1, 5, null)
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          bondConfig: bondConfigPda1,
+          rewardsConfig: rewardsConfigPda,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          bond: bond5,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+          merkleTree: null,
+          treeConfig: null,
+          assetAuthority: null,
+          bubblegumProgram: null,
+          logWrapper: null,
+          compressionProgram: null,
+          programConfig: programConfigPda,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Unbonding required"
+      );
+    }
+  });
+
+  it("Start unbond bond 5 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    let userAccBefore = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    await program.methods
+      .startUnbond(1, 5)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond5,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond5);
+
+    expect(bondAcc.state).to.equal(2);
+    expect(bondAcc.cooldownEndTimestamp.toNumber()).to.equal(
+      bondAcc.unbondTimestamp.toNumber() + 1000
+    );
+
+    let userAccAfter = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    expect(
+      userAccBefore.addressTotalBondAmount
+        .sub(userAccAfter.addressTotalBondAmount)
+        .toNumber()
+    ).to.equal(100e9);
+  });
+
+  it("Complete withdraw bond 5 by user - cooldown not over (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .completeWithdraw(1, 5, null)
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          bondConfig: bondConfigPda1,
+          rewardsConfig: rewardsConfigPda,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          bond: bond5,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+          merkleTree: null,
+          treeConfig: null,
+          assetAuthority: null,
+          bubblegumProgram: null,
+          logWrapper: null,
+          compressionProgram: null,
+          programConfig: programConfigPda,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Cooldown not over"
+      );
+    }
+  });
+
+  it("Cancel unbond bond 5 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .cancelUnbond(1, 5)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond5,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond5);
+
+    expect(bondAcc.state).to.equal(1);
+    expect(bondAcc.cooldownEndTimestamp.toNumber()).to.equal(0);
+    // the penalty is only charged by complete withdraw
+    expect(bondAcc.bondAmount.toNumber()).to.equal(100e9);
+    expect(bondAcc.unbondPenalty.toNumber()).to.equal(0);
+
+    await program.methods
+      .updateCooldownPeriod(1, new anchor.BN(0))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Close bond 4 by user - bond is active (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .closeBond(4)
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          bond: bond4,
+          assetUsage: await assetUsageOf(bond4),
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Bond is active"
+      );
+    }
+  });
+
+  it("Close bond 2 by user - vault bond cannot be closed (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond2 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(2).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .closeBond(2)
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          bond: bond2,
+          assetUsage: await assetUsageOf(bond2),
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Vault bond cannot be closed"
+      );
+    }
+  });
+
+  it("Close bond 1 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond1 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(1).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const assetUsage1 = await assetUsageOf(bond1);
+
+    let userAccBefore = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    await program.methods
+      .closeBond(1)
+      .signers([user])
+      .accounts({
+        addressBondsRewards: userBondsRewards,
+        bond: bond1,
+        assetUsage: assetUsage1,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetchNullable(bond1);
+
+    expect(bondAcc).to.be.null;
+
+    let assetUsageAcc = await program.account.assetUsage.fetchNullable(
+      assetUsage1
+    );
+
+    expect(assetUsageAcc).to.be.null;
+
+    let userAccAfter = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    expect(userAccAfter.openBonds).to.equal(userAccBefore.openBonds - 1);
+  });
+
+  it("Close address by user - open bonds (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .closeAddress()
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Address has open bonds"
+      );
+    }
+  });
+
+  it("Enable auto renew bond 4 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .enableAutoRenew(1, 4)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond4,
+        assetUsage: await assetUsageOf(bond4),
+        owner: user.publicKey,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond4);
+
+    expect(bondAcc.autoRenew).to.equal(1);
+  });
+
+  it("Disable auto renew bond 4 by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .disableAutoRenew(1, 4)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond4,
+        assetUsage: await assetUsageOf(bond4),
+        owner: user.publicKey,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond4);
+    let bondConfigAcc = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    expect(bondAcc.autoRenew).to.equal(0);
+    expect(bondAcc.unbondTimestamp.toNumber()).to.equal(
+      bondAcc.bondTimestamp.toNumber() + bondConfigAcc.lockPeriod.toNumber()
+    );
+  });
+
+  it("Disable auto renew bond 4 by user - already disabled (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .disableAutoRenew(1, 4)
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          addressBondsRewards: userBondsRewards,
+          bond: bond4,
+          assetUsage: await assetUsageOf(bond4),
+          owner: user.publicKey,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Auto renew is disabled"
+      );
+    }
+  });
+
+  it("Renew bond 4 of user by user2 - no keeper (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .renew(1, 4)
+        .signers([user2])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          addressBondsRewards: userBondsRewards,
+          bond: bond4,
+          assetUsage: await assetUsageOf(bond4),
+          owner: user.publicKey,
+          authority: user2.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Not privileged"
+      );
+    }
+  });
+
+  it("Set user2 as keeper by user", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    await program.methods
+      .updateKeeper(user2.publicKey)
+      .signers([user])
+      .accounts({
+        addressBondsRewards: userBondsRewards,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    let userAcc = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    expect(userAcc.keeper.toBase58()).to.equal(user2.publicKey.toBase58());
+  });
+
+  it("Renew bond 4 of user by keeper user2", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .renew(1, 4)
+      .signers([user2])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond4,
+        assetUsage: await assetUsageOf(bond4),
+        owner: user.publicKey,
+        authority: user2.publicKey,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond4);
+    let bondConfigAcc = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    expect(bondAcc.unbondTimestamp.toNumber()).to.equal(
+      bondAcc.bondTimestamp.toNumber() + bondConfigAcc.lockPeriod.toNumber()
+    );
+  });
+
+  it("Migrate user address - already migrated (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .migrateAddress(user.publicKey)
+        .signers([user2])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          payer: user2.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Account already migrated"
+      );
+    }
+  });
+
+  it("Emergency withdraw bond 5 by user - emergency inactive (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .emergencyWithdraw(1, 5, null)
+        .signers([user])
+        .accounts({
+          addressBondsRewards: userBondsRewards,
+          bondConfig: bondConfigPda1,
+          rewardsConfig: rewardsConfigPda,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          bond: bond5,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+          merkleTree: null,
+          treeConfig: null,
+          assetAuthority: null,
+          bubblegumProgram: null,
+          logWrapper: null,
+          compressionProgram: null,
+          programConfig: programConfigPda,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Emergency state is inactive"
+      );
+    }
+  });
+
+  it("Set emergency state active by admin", async () => {
+    await program.methods
+      .setEmergencyStateActive()
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let programConfig = await program.account.programConfig.fetch(
+      programConfigPda
+    );
+
+    expect(programConfig.emergencyState).to.equal(1);
+  });
+
+  it("Emergency withdraw bond 5 by user - bond config paused", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond5 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(5).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .setBondStateInactive(1)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bondAccBefore = await program.account.bond.fetch(bond5);
+
+    let userBalanceBefore = (
+      await program.provider.connection.getTokenAccountBalance(
+        itheum_token_user_ata
+      )
+    ).value.amount;
+
+    await program.methods
+      .emergencyWithdraw(1, 5, null)
+      .signers([user])
+      .accounts({
+        addressBondsRewards: userBondsRewards,
+        bondConfig: bondConfigPda1,
+        rewardsConfig: rewardsConfigPda,
+        mintOfTokenToReceive: itheum_token_mint.publicKey,
+        bond: bond5,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        authority: user.publicKey,
+        authorityTokenAccount: itheum_token_user_ata,
+        merkleTree: null,
+        treeConfig: null,
+        assetAuthority: null,
+        bubblegumProgram: null,
+        logWrapper: null,
+        compressionProgram: null,
+        programConfig: programConfigPda,
+      })
+      .rpc();
+
+    let userBalanceAfter = (
+      await program.provider.connection.getTokenAccountBalance(
+        itheum_token_user_ata
+      )
+    ).value.amount;
+
+    let bondAcc = await program.account.bond.fetch(bond5);
+
+    expect(bondAcc.state).to.equal(0);
+    expect(bondAcc.bondAmount.toNumber()).to.equal(0);
+    expect(Number(userBalanceAfter) - Number(userBalanceBefore)).to.equal(
+      bondAccBefore.bondAmount.toNumber()
+    );
+
+    await program.methods
+      .setBondStateActive(1)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .setEmergencyStateInactive()
+      .signers([admin])
+      .accounts({
+        programConfig: programConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Update pause flags by user (should fail)", async () => {
+    try {
+      await program.methods
+        .updatePauseFlags(1, 2)
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2012);
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "An address constraint was violated"
+      );
+    }
+  });
+
+  it("Update global pause flags by admin - wrong value (should fail)", async () => {
+    try {
+      await program.methods
+        .updateGlobalPauseFlags(64)
+        .signers([admin])
+        .accounts({
+          programConfig: programConfigPda,
+          authority: admin.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Wrong value"
+      );
+    }
+  });
+
+  it("Renew bond 4 by user - renew paused (should fail)", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    await program.methods
+      .updatePauseFlags(1, 2)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    let bondConfigAcc = await program.account.bondConfig.fetch(bondConfigPda1);
+
+    expect(bondConfigAcc.pauseFlags).to.equal(2);
+
+    try {
+      await program.methods
+        .renew(1, 4)
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
+          rewardsConfig: rewardsConfigPda,
+          vaultConfig: vaultConfigPda,
+          addressBondsRewards: userBondsRewards,
+          bond: bond4,
+          assetUsage: await assetUsageOf(bond4),
+          owner: user.publicKey,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Program is paused"
+      );
+    }
+
+    await program.methods
+      .updatePauseFlags(1, 0)
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+  });
+
+  it("TopUp bond 4 by user - not the vault bond", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    let bondAccBefore = await program.account.bond.fetch(bond4);
+
+    await program.methods
+      .topUp(1, 4, new anchor.BN(10e9))
+      .signers([user])
+      .accounts({
+        addressBondsRewards: userBondsRewards,
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        mintOfTokenSent: itheum_token_mint.publicKey,
+        bond: bond4,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        authority: user.publicKey,
+        authorityTokenAccount: itheum_token_user_ata,
+      })
+      .rpc();
+
+    let bondAcc = await program.account.bond.fetch(bond4);
+
+    expect(
+      bondAcc.bondAmount.sub(bondAccBefore.bondAmount).toNumber()
+    ).to.equal(10e9);
+  });
+
+  it("Check user aggregate liveliness", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    let userAcc = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+    let bondAcc = await program.account.bond.fetch(bond4);
+
+    // bond 4 is the only active bond left
+    expect(userAcc.livelinessAmount.toString()).to.equal(
+      bondAcc.bondAmount.toString()
+    );
+    expect(userAcc.livelinessUnbondSum.toString()).to.equal(
+      bondAcc.bondAmount.mul(bondAcc.unbondTimestamp).toString()
+    );
+    expect(userAcc.livelinessLockSum.toString()).to.equal(
+      bondAcc.bondAmount
+        .mul(bondAcc.unbondTimestamp.sub(bondAcc.bondTimestamp))
+        .toString()
+    );
+    expect(userAcc.livelinessAutoRenewLockSum.toNumber()).to.equal(0);
+  });
+
+  it("Update liveliness curve by user (should fail)", async () => {
+    try {
+      await program.methods
+        .updateLivelinessCurve(1, new anchor.BN(90_00), new anchor.BN(50_00))
+        .signers([user])
+        .accounts({
+          rewardsConfig: rewardsConfigPda,
+          authority: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorCode.number).to.equal(2012);
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "An address constraint was violated"
+      );
+    }
+  });
+
+  it("Update liveliness curve with unknown curve (should fail)", async () => {
+    try {
+      await program.methods
+        .updateLivelinessCurve(3, new anchor.BN(90_00), new anchor.BN(50_00))
+        .signers([admin])
+        .accounts({
+          rewardsConfig: rewardsConfigPda,
+          authority: admin.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Wrong value"
+      );
+    }
+  });
+
+  it("Update liveliness curve by admin", async () => {
+    let rewards_config = await program.account.rewardsConfig.fetch(
+      rewardsConfigPda
+    );
+
+    assert(rewards_config.livelinessCurve == 0);
+    assert(rewards_config.livelinessThreshold.eq(new anchor.BN(95_00)));
+
+    await program.methods
+      .updateLivelinessCurve(1, new anchor.BN(90_00), new anchor.BN(50_00))
+      .signers([admin])
+      .accounts({
+        rewardsConfig: rewardsConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    rewards_config = await program.account.rewardsConfig.fetch(
+      rewardsConfigPda
+    );
+
+    assert(rewards_config.livelinessCurve == 1);
+    assert(rewards_config.livelinessThreshold.eq(new anchor.BN(90_00)));
+    assert(rewards_config.livelinessCurveParam.eq(new anchor.BN(50_00)));
+
+    await program.methods
+      .updateLivelinessCurve(0, new anchor.BN(95_00), new anchor.BN(0))
+      .signers([admin])
+      .accounts({
+        rewardsConfig: rewardsConfigPda,
+        authority: admin.publicKey,
+      })
+      .rpc();
+  });
+
+  it("View pending rewards, liveliness and apr", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const accountsBefore = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+
+    const claimable = await program.methods
+      .getClaimableRewards(user.publicKey)
+      .accounts({
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+      })
+      .view();
+
+    const liveliness = await program.methods
+      .getAddressLiveliness(user.publicKey)
+      .accounts({
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+      })
+      .view();
+
+    const apr = await program.methods
+      .getCurrentApr()
+      .accounts({
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+      })
+      .view();
+
+    const rewards_config = await program.account.rewardsConfig.fetch(
+      rewardsConfigPda
+    );
+
+    assert(claimable.gte(new anchor.BN(0)));
+    assert(liveliness.lte(new anchor.BN(10000)));
+    assert(apr.lte(rewards_config.maxApr));
+
+    // views do not write to the accounts
+    const accountsAfter = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+    assert(accountsAfter.claimableAmount.eq(accountsBefore.claimableAmount));
+    assert(
+      accountsAfter.addressRewardsPerShare.eq(
+        accountsBefore.addressRewardsPerShare
+      )
+    );
+  });
+
+  it("Get bond status by asset id", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const bondAcc = await program.account.bond.fetch(bond4);
+
+    const assetUsage = PublicKey.findProgramAddressSync(
+      [bondAcc.assetId.toBuffer()],
+      program.programId
+    )[0];
+
+    const status = await program.methods
+      .getBondStatus(bondAcc.assetId)
+      .accounts({
+        assetUsage: assetUsage,
+        bond: bond4,
+        bondConfig: bondConfigPda1,
+        addressBondsRewards: userBondsRewards,
+      })
+      .view();
+
+    expect(status.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(status.state).to.equal(1);
+    assert(status.bondAmount.eq(bondAcc.bondAmount));
+    if (bondAcc.autoRenew == 1) {
+      assert(status.unbondTimestamp.gte(bondAcc.unbondTimestamp));
+    } else {
+      assert(status.unbondTimestamp.eq(bondAcc.unbondTimestamp));
+    }
+    assert(status.liveliness.lte(new anchor.BN(10000)));
+  });
+
+  it("Resolve bond from asset usage", async () => {
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const bondAcc = await program.account.bond.fetch(bond4);
+
+    const assetUsage = PublicKey.findProgramAddressSync(
+      [bondAcc.assetId.toBuffer()],
+      program.programId
+    )[0];
+
+    const assetUsageAcc = await program.account.assetUsage.fetch(assetUsage);
+
+    expect(assetUsageAcc.bond.toBase58()).to.equal(bond4.toBase58());
+    expect(assetUsageAcc.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(assetUsageAcc.bondId).to.equal(4);
+    expect(assetUsageAcc.bondConfigIndex).to.equal(1);
+  });
+
+  it("Migrate already migrated asset usage (should fail)", async () => {
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    const bondAcc = await program.account.bond.fetch(bond4);
+
+    const assetUsage = PublicKey.findProgramAddressSync(
+      [bondAcc.assetId.toBuffer()],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .migrateAssetUsage(bondAcc.assetId, 1, 4, new anchor.BN(0))
+        .signers([user])
+        .accounts({
+          assetUsage: assetUsage,
+          bondConfig: bondConfigPda1,
+          bond: bond4,
+          payer: user.publicKey,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Account already migrated"
+      );
+    }
+  });
+
+  it("Partial withdraw bond 4 by user - after lock period", async () => {
+    const userBondsRewards = PublicKey.findProgramAddressSync(
+      [Buffer.from("address_bonds_rewards"), user.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    const bond4 = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bond"),
+        user.publicKey.toBuffer(),
+        new anchor.BN(4).toBuffer("le", 2),
+      ],
+      program.programId
+    )[0];
+
+    // partial withdraw needs no cooldown, renew relocks bond 4 for a second
+    await program.methods
+      .updateCooldownPeriod(1, new anchor.BN(0))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .updateLockPeriod(1, new anchor.BN(1))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .renew(1, 4)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        rewardsConfig: rewardsConfigPda,
+        vaultConfig: vaultConfigPda,
+        addressBondsRewards: userBondsRewards,
+        bond: bond4,
+        assetUsage: await assetUsageOf(bond4),
+        owner: user.publicKey,
+        authority: user.publicKey,
+      })
+      .rpc();
+
+    await new Promise((r) => setTimeout(r, 2000));
+
+    const bondConfigAcc = await program.account.bondConfig.fetch(
+      bondConfigPda1
+    );
+    const bondAccBefore = await program.account.bond.fetch(bond4);
+    const userAccBefore = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+    const vaultConfigBefore = await program.account.vaultConfig.fetch(
+      vaultConfigPda
+    );
+    const userBalanceBefore = (
+      await program.provider.connection.getTokenAccountBalance(
+        itheum_token_user_ata
+      )
+    ).value.amount;
+
+    // everything above the config bond amount can be withdrawn
+    const amount = bondAccBefore.bondAmount.sub(bondConfigAcc.bondAmount);
+
+    assert(amount.gt(new anchor.BN(0)));
+
+    try {
+      await program.methods
+        .partialWithdraw(1, 4, amount.add(new anchor.BN(1)))
+        .signers([user])
+        .accounts({
+          bondConfig: bondConfigPda1,
+          programConfig: programConfigPda,
+          addressBondsRewards: userBondsRewards,
+          rewardsConfig: rewardsConfigPda,
+          bond: bond4,
+          vaultConfig: vaultConfigPda,
+          vault: vault_ata,
+          mintOfTokenToReceive: itheum_token_mint.publicKey,
+          authority: user.publicKey,
+          authorityTokenAccount: itheum_token_user_ata,
+        })
+        .rpc();
+      assert(false, "Should have thrown error");
+    } catch (err) {
+      expect((err as anchor.AnchorError).error.errorMessage).to.equal(
+        "Bond amount too low"
+      );
+    }
+
+    await program.methods
+      .partialWithdraw(1, 4, amount)
+      .signers([user])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        programConfig: programConfigPda,
+        addressBondsRewards: userBondsRewards,
+        rewardsConfig: rewardsConfigPda,
+        bond: bond4,
+        vaultConfig: vaultConfigPda,
+        vault: vault_ata,
+        mintOfTokenToReceive: itheum_token_mint.publicKey,
+        authority: user.publicKey,
+        authorityTokenAccount: itheum_token_user_ata,
+      })
+      .rpc();
+
+    const bondAcc = await program.account.bond.fetch(bond4);
+    const userAcc = await program.account.addressBondsRewards.fetch(
+      userBondsRewards
+    );
+    const vaultConfig = await program.account.vaultConfig.fetch(vaultConfigPda);
+    const userBalanceAfter = (
+      await program.provider.connection.getTokenAccountBalance(
+        itheum_token_user_ata
+      )
+    ).value.amount;
+
+    expect(bondAcc.state).to.equal(1);
+    assert(bondAcc.bondAmount.eq(bondConfigAcc.bondAmount));
+    assert(
+      userAcc.addressTotalBondAmount.eq(
+        userAccBefore.addressTotalBondAmount.sub(amount)
+      )
+    );
+    assert(
+      vaultConfig.totalBondAmount.eq(
+        vaultConfigBefore.totalBondAmount.sub(amount)
+      )
+    );
+    expect(Number(userBalanceAfter) - Number(userBalanceBefore)).to.equal(
+      amount.toNumber()
+    );
+
+    // bond 4 is the only active bond, re-added at the new amount past its lock so only
+    // its lock period counts
+    assert(bondAcc.lockExpired == 1);
+    expect(userAcc.livelinessAmount.toNumber()).to.equal(0);
+    expect(userAcc.livelinessUnbondSum.toNumber()).to.equal(0);
+    expect(userAcc.livelinessLockSum.toString()).to.equal(
+      bondAcc.bondAmount
+        .mul(bondAcc.unbondTimestamp.sub(bondAcc.bondTimestamp))
+        .toString()
+    );
+
+    await program.methods
+      .updateLockPeriod(1, new anchor.BN(1000))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .updateCooldownPeriod(1, new anchor.BN(1000))
+      .signers([admin])
+      .accounts({
+        bondConfig: bondConfigPda1,
+        authority: admin.publicKey,
+      })
+      .rpc();
+  });
 });